@@ -0,0 +1,23 @@
+use crate::deserialize::{Deserialize, Deserializer};
+use crate::serialize::{Serialize, Serializer};
+use crate::Value;
+use alloc::vec::Vec;
+
+/// A wrapper which causes its contents to be serialized and deserialized as a byte string,
+/// rather than as a list of integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Bytes<T: ?Sized = Vec<u8>>(pub T);
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: AsRef<[u8]> + ?Sized> Serialize<S, Ctx> for Bytes<T> {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_bytes(self.0.as_ref())
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: From<Vec<u8>>> Deserialize<D, Ctx> for Bytes<T> {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        Ok(Bytes(value.get_bytes()?.into_owned().into()))
+    }
+}
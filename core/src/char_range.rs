@@ -0,0 +1,46 @@
+use crate::deserialize::{Deserialize, Deserializer, InvalidCharRangeError};
+use crate::serialize::{Serialize, Serializer};
+use crate::Value;
+use core::ops::RangeInclusive;
+
+/// A wrapper which causes an inclusive range of characters to be serialized and deserialized as a
+/// string of the form `"a-z"`, rather than as a struct with `start` and `end` fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CharRange(pub RangeInclusive<char>);
+
+impl From<RangeInclusive<char>> for CharRange {
+    fn from(range: RangeInclusive<char>) -> Self {
+        CharRange(range)
+    }
+}
+
+impl core::fmt::Display for CharRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}-{}", self.0.start(), self.0.end())
+    }
+}
+
+impl core::str::FromStr for CharRange {
+    type Err = InvalidCharRangeError;
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let mut chars = str.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some(start), Some('-'), Some(end), None) => Ok(CharRange(start..=end)),
+            _ => Err(InvalidCharRangeError),
+        }
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for CharRange {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for CharRange {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
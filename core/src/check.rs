@@ -0,0 +1,748 @@
+use crate::{Deserializer, NameMap, Number, Outliner, Serializer, ShapeDeserializer};
+use crate::deserialize::{ValueKind, NO_CHECKPOINT_ERROR};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// An [`Outliner`] which wraps another [`Outliner`], tracking the state of the stack and
+/// panicking with a descriptive message when it is used incorrectly. For example, calling
+/// [`Outliner::push_field`] while a string is open, or finishing without closing a list.
+///
+/// The [`crate::Value`], [`crate::Struct`], [`crate::Tuple`], [`crate::List`] and
+/// [`crate::Map`] helpers already enforce correct usage, so this is mainly useful for testing
+/// code which uses the raw [`Serializer`] and [`Deserializer`] interfaces, including new format
+/// implementations. Once a call on the underlying [`Outliner`] returns an error, the state of
+/// the stack is no longer tracked.
+pub struct Checker<O> {
+    inner: O,
+    state: CheckState,
+}
+
+/// The state of the stack, as tracked by a [`Checker`].
+struct CheckState {
+    stack: Vec<Item>,
+    failed: bool,
+
+    /// For each checkpoint taken using [`Deserializer::checkpoint`], the length of the stack and
+    /// the value of `failed` at the time it was taken.
+    checkpoints: Vec<(usize, bool)>,
+}
+
+/// An item on the stack, as tracked by a [`Checker`].
+#[derive(Debug, Clone, Copy)]
+enum Item {
+    Value,
+    Str,
+    Bytes,
+    Struct,
+    Tuple,
+    List { rem_len: Option<usize> },
+    Map { rem_len: Option<usize>, at_value: bool },
+}
+
+/// Identifies a type of [`Item`], for the purposes of checking which items a method can be
+/// called on.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Kind {
+    Value,
+    Str,
+    Bytes,
+    Struct,
+    Tuple,
+    List,
+    Map,
+}
+
+impl Item {
+    /// Gets the [`Kind`] of this item.
+    fn kind(&self) -> Kind {
+        match self {
+            Item::Value => Kind::Value,
+            Item::Str => Kind::Str,
+            Item::Bytes => Kind::Bytes,
+            Item::Struct => Kind::Struct,
+            Item::Tuple => Kind::Tuple,
+            Item::List { .. } => Kind::List,
+            Item::Map { .. } => Kind::Map,
+        }
+    }
+}
+
+impl Kind {
+    /// Gets a description of this kind of item, for use in panic messages.
+    fn describe(self) -> &'static str {
+        match self {
+            Kind::Value => "a value",
+            Kind::Str => "an opened string",
+            Kind::Bytes => "an opened byte string",
+            Kind::Struct => "an opened struct",
+            Kind::Tuple => "an opened tuple",
+            Kind::List => "an opened list",
+            Kind::Map => "an opened map",
+        }
+    }
+}
+
+impl CheckState {
+    /// Asserts that the top item on the stack is of the given [`Kind`], returning it.
+    fn check(&mut self, method: &str, kind: Kind) -> Option<&mut Item> {
+        if self.failed {
+            return None;
+        }
+        match self.stack.last_mut() {
+            Some(item) if item.kind() == kind => Some(item),
+            Some(item) => panic!(
+                "`{}` requires {} on top of the stack, but found {}",
+                method,
+                kind.describe(),
+                item.kind().describe()
+            ),
+            None => panic!(
+                "`{}` requires {} on top of the stack, but the stack is empty",
+                method,
+                kind.describe()
+            ),
+        }
+    }
+
+    /// Updates the stack after a call whose preconditions were verified using
+    /// [`CheckState::check`].
+    fn update<R, E>(&mut self, res: &Result<R, E>, f: impl FnOnce(&mut Vec<Item>, &R)) {
+        match res {
+            Ok(res) => {
+                if !self.failed {
+                    f(&mut self.stack, res)
+                }
+            }
+            Err(_) => self.failed = true,
+        }
+    }
+
+    /// Asserts that the top item on the stack is a value, and replaces it with the given item
+    /// if the call succeeds.
+    fn open<R, E>(
+        &mut self,
+        method: &str,
+        res: impl FnOnce() -> Result<R, E>,
+        item: Item,
+    ) -> Result<R, E> {
+        self.check(method, Kind::Value);
+        let res = res();
+        self.update(&res, |stack, _| *stack.last_mut().unwrap() = item);
+        res
+    }
+}
+
+/// Asserts that an opened list or map with the given remaining length can have another item.
+fn check_push(method: &str, rem_len: &mut Option<usize>) {
+    if let Some(rem_len) = rem_len {
+        assert!(
+            *rem_len > 0,
+            "`{}` was called after all items of a sized collection were written",
+            method
+        );
+        *rem_len -= 1;
+    }
+}
+
+/// Asserts that an opened list or map with the given remaining length can be closed.
+fn check_close(method: &str, rem_len: Option<usize>) {
+    if let Some(rem_len) = rem_len {
+        assert!(
+            rem_len == 0,
+            "`{}` was called with {} items of a sized collection left unwritten",
+            method,
+            rem_len
+        );
+    }
+}
+
+impl<O> Checker<O> {
+    /// Constructs a new [`Checker`] wrapping the given [`Outliner`]. The stack of the
+    /// [`Outliner`] should consist of a single value item.
+    pub fn new(inner: O) -> Self {
+        Self {
+            inner,
+            state: CheckState {
+                stack: vec![Item::Value],
+                failed: false,
+                checkpoints: Vec::new(),
+            },
+        }
+    }
+
+    /// Gets the underlying [`Outliner`].
+    pub fn inner(&self) -> &O {
+        &self.inner
+    }
+
+    /// Asserts that the stack is empty, i.e. that the value was completely written or read, and
+    /// returns the underlying [`Outliner`].
+    pub fn finish(self) -> O {
+        if !self.state.failed {
+            if let Some(item) = self.state.stack.last() {
+                panic!(
+                    "finished with {} left on top of the stack",
+                    item.kind().describe()
+                );
+            }
+        }
+        self.inner
+    }
+}
+
+impl<O: Outliner> Outliner for Checker<O> {
+    type Error = O::Error;
+
+    fn supports_null(&self) -> bool {
+        self.inner.supports_null()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.state.check("pop_null", Kind::Value);
+        assert!(
+            self.inner.supports_null(),
+            "`pop_null` was called, but `supports_null` is false"
+        );
+        let res = self.inner.pop_null();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        self.state.open("open_str", || self.inner.open_str(), Item::Str)
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        self.state.check("close_str", Kind::Str);
+        let res = self.inner.close_str();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        self.state.open("open_bytes", || self.inner.open_bytes(), Item::Bytes)
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        self.state.check("close_bytes", Kind::Bytes);
+        let res = self.inner.close_bytes();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let res = || self.inner.open_struct(type_name);
+        self.state.open("open_struct", res, Item::Struct)
+    }
+
+    fn open_struct_with_fields(
+        &mut self,
+        type_name: Option<&'static str>,
+        fields: &'static [&'static str],
+    ) -> Result<(), Self::Error> {
+        let res = || self.inner.open_struct_with_fields(type_name, fields);
+        self.state.open("open_struct_with_fields", res, Item::Struct)
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.state.check("push_field", Kind::Struct);
+        let res = self.inner.push_field(name);
+        self.state.update(&res, |stack, _| stack.push(Item::Value));
+        res
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        self.state.check("close_struct", Kind::Struct);
+        let res = self.inner.close_struct();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let res = || self.inner.open_tuple(type_name);
+        self.state.open("open_tuple", res, Item::Tuple)
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        self.state.check("push_element", Kind::Tuple);
+        let res = self.inner.push_element();
+        self.state.update(&res, |stack, _| stack.push(Item::Value));
+        res
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        self.state.check("close_tuple", Kind::Tuple);
+        let res = self.inner.close_tuple();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        if let Some(Item::List { rem_len }) = self.state.check("push_item", Kind::List) {
+            check_push("push_item", rem_len);
+        }
+        let res = self.inner.push_item();
+        self.state.update(&res, |stack, _| stack.push(Item::Value));
+        res
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        if let Some(Item::List { rem_len }) = self.state.check("close_list", Kind::List) {
+            check_close("close_list", *rem_len);
+        }
+        let res = self.inner.close_list();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        if let Some(Item::Map { rem_len, at_value }) = self.state.check("push_key", Kind::Map) {
+            assert!(!*at_value, "`push_key` was called before the previous entry's value");
+            check_push("push_key", rem_len);
+            *at_value = true;
+        }
+        let res = self.inner.push_key();
+        self.state.update(&res, |stack, _| stack.push(Item::Value));
+        res
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        if let Some(Item::Map { at_value, .. }) = self.state.check("push_value", Kind::Map) {
+            assert!(*at_value, "`push_value` was called without a preceding key");
+            *at_value = false;
+        }
+        let res = self.inner.push_value();
+        self.state.update(&res, |stack, _| stack.push(Item::Value));
+        res
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        if let Some(Item::Map { rem_len, at_value }) = self.state.check("close_map", Kind::Map) {
+            assert!(!*at_value, "`close_map` was called before the last entry's value");
+            check_close("close_map", *rem_len);
+        }
+        let res = self.inner.close_map();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+}
+
+/// Implements a method of [`Serializer`] for [`Checker`] which pops a value from the stack.
+macro_rules! check_put {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self, value: $ty) -> Result<(), Self::Error> {
+            self.state.check(stringify!($name), Kind::Value);
+            let res = self.inner.$name(value);
+            self.state.update(&res, |stack, _| {
+                stack.pop();
+            });
+            res
+        }
+    };
+}
+
+impl<S: Serializer> Serializer for Checker<S> {
+    check_put!(put_bool, bool);
+    check_put!(put_i8, i8);
+    check_put!(put_i16, i16);
+    check_put!(put_i32, i32);
+    check_put!(put_i64, i64);
+    check_put!(put_i128, i128);
+    check_put!(put_u8, u8);
+    check_put!(put_u16, u16);
+    check_put!(put_u32, u32);
+    check_put!(put_u64, u64);
+    check_put!(put_u128, u128);
+    check_put!(put_f32, f32);
+    check_put!(put_f64, f64);
+    check_put!(put_char, char);
+    check_put!(put_str, &str);
+    check_put!(put_bytes, &[u8]);
+
+    fn append_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.state.check("append_char", Kind::Str);
+        self.inner.append_char(value).inspect_err(|_| self.state.failed = true)
+    }
+
+    fn append_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.state.check("append_str", Kind::Str);
+        self.inner.append_str(value).inspect_err(|_| self.state.failed = true)
+    }
+
+    fn append_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.state.check("append_bytes", Kind::Bytes);
+        self.inner.append_bytes(value).inspect_err(|_| self.state.failed = true)
+    }
+
+    fn put_tag(
+        &mut self,
+        max_index: usize,
+        index: usize,
+        name: Option<&'static str>,
+    ) -> Result<(), Self::Error> {
+        self.state.check("put_tag", Kind::Value);
+        assert!(
+            index <= max_index,
+            "`put_tag` was called with index {} greater than max index {}",
+            index,
+            max_index
+        );
+        let res = self.inner.put_tag(max_index, index, name);
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn open_list_sized(&mut self, len: usize) -> Result<(), Self::Error> {
+        let res = || self.inner.open_list_sized(len);
+        let item = Item::List { rem_len: Some(len) };
+        self.state.open("open_list_sized", res, item)
+    }
+
+    fn open_map_sized(&mut self, len: usize, str_keys: bool) -> Result<(), Self::Error> {
+        let res = || self.inner.open_map_sized(len, str_keys);
+        let item = Item::Map {
+            rem_len: Some(len),
+            at_value: false,
+        };
+        self.state.open("open_map_sized", res, item)
+    }
+
+    fn supports_unsized(&self) -> bool {
+        self.inner.supports_unsized()
+    }
+
+    fn open_list_unsized(&mut self) -> Result<(), Self::Error> {
+        assert!(
+            self.inner.supports_unsized(),
+            "`open_list_unsized` was called, but `supports_unsized` is false"
+        );
+        let res = || self.inner.open_list_unsized();
+        self.state.open("open_list_unsized", res, Item::List { rem_len: None })
+    }
+
+    fn open_map_unsized(&mut self, str_keys: bool) -> Result<(), Self::Error> {
+        assert!(
+            self.inner.supports_unsized(),
+            "`open_map_unsized` was called, but `supports_unsized` is false"
+        );
+        let res = || self.inner.open_map_unsized(str_keys);
+        let item = Item::Map {
+            rem_len: None,
+            at_value: false,
+        };
+        self.state.open("open_map_unsized", res, item)
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        self.inner.error(source)
+    }
+}
+
+/// Implements a method of [`Deserializer`] for [`Checker`] which pops a value from the stack.
+macro_rules! check_get {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self) -> Result<$ty, Self::Error> {
+            self.state.check(stringify!($name), Kind::Value);
+            let res = self.inner.$name();
+            self.state.update(&res, |stack, _| {
+                stack.pop();
+            });
+            res
+        }
+    };
+}
+
+impl<D: Deserializer> Deserializer for Checker<D> {
+    check_get!(get_bool, bool);
+    check_get!(get_i8, i8);
+    check_get!(get_i16, i16);
+    check_get!(get_i32, i32);
+    check_get!(get_i64, i64);
+    check_get!(get_i128, i128);
+    check_get!(get_u8, u8);
+    check_get!(get_u16, u16);
+    check_get!(get_u32, u32);
+    check_get!(get_u64, u64);
+    check_get!(get_u128, u128);
+    check_get!(get_f32, f32);
+    check_get!(get_f64, f64);
+    check_get!(get_char, char);
+    check_get!(read_str, Cow<'_, str>);
+    check_get!(read_bytes, Cow<'_, [u8]>);
+
+    fn next_char(&mut self) -> Result<Option<char>, Self::Error> {
+        self.state.check("next_char", Kind::Str);
+        let res = self.inner.next_char();
+        self.state.update(&res, |stack, res| {
+            if res.is_none() {
+                stack.pop();
+            }
+        });
+        res
+    }
+
+    fn flush_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
+        self.state.check("flush_str", Kind::Str);
+        let res = self.inner.flush_str();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn skip_str(&mut self) -> Result<(), Self::Error> {
+        self.state.check("skip_str", Kind::Str);
+        let res = self.inner.skip_str();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        self.state.check("next_bytes_chunk", Kind::Bytes);
+        let res = self.inner.next_bytes_chunk();
+        self.state.update(&res, |stack, res| {
+            if res.is_none() {
+                stack.pop();
+            }
+        });
+        res
+    }
+
+    fn flush_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        self.state.check("flush_bytes", Kind::Bytes);
+        let res = self.inner.flush_bytes();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn flush_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        self.state.check("flush_name", Kind::Str);
+        let res = self.inner.flush_name(names);
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn get_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        self.state.check("get_name", Kind::Value);
+        let res = self.inner.get_name(names);
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        self.state.check("get_tag", Kind::Value);
+        let res = self.inner.get_tag(max_index, names);
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn check_null(&mut self) -> Result<bool, Self::Error> {
+        self.state.check("check_null", Kind::Value);
+        assert!(
+            self.inner.supports_null(),
+            "`check_null` was called, but `supports_null` is false"
+        );
+        let res = self.inner.check_null();
+        self.state.update(&res, |stack, res| {
+            if *res {
+                stack.pop();
+            }
+        });
+        res
+    }
+
+    fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = || self.inner.open_list();
+        self.state.open("open_list", res, Item::List { rem_len: None })
+    }
+
+    fn next_item(&mut self) -> Result<bool, Self::Error> {
+        self.state.check("next_item", Kind::List);
+        let res = self.inner.next_item();
+        self.state.update(&res, |stack, res| {
+            if *res {
+                stack.push(Item::Value);
+            } else {
+                stack.pop();
+            }
+        });
+        res
+    }
+
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = || self.inner.open_map();
+        let item = Item::Map {
+            rem_len: None,
+            at_value: false,
+        };
+        self.state.open("open_map", res, item)
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        if let Some(Item::Map { at_value, .. }) = self.state.check("next_key", Kind::Map) {
+            assert!(!*at_value, "`next_key` was called before the previous entry's value");
+        }
+        let res = self.inner.next_key();
+        self.state.update(&res, |stack, res| {
+            if *res {
+                if let Some(Item::Map { at_value, .. }) = stack.last_mut() {
+                    *at_value = true;
+                }
+                stack.push(Item::Value);
+            } else {
+                stack.pop();
+            }
+        });
+        res
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        if !self.state.failed {
+            let top = self.state.stack.last().map(Item::kind);
+            assert!(
+                top == Some(Kind::Value),
+                "`peek_kind` requires a value on top of the stack"
+            );
+        }
+        self.inner.peek_kind()
+    }
+
+    fn recovery_depth(&self) -> Option<usize> {
+        self.inner.recovery_depth()
+    }
+
+    fn recover(&mut self, depth: usize, err: Self::Error) -> Result<(), Self::Error> {
+        self.inner.recover(depth, err)
+    }
+
+    fn unwind(&mut self, depth: usize) -> bool {
+        self.inner.unwind(depth)
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        self.state.check("checkpoint", Kind::Value);
+        let res = self.inner.checkpoint();
+        if let Ok(true) = res {
+            let len = self.state.stack.len();
+            self.state.checkpoints.push((len, self.state.failed));
+        }
+        res
+    }
+
+    fn rewind(&mut self) {
+        let (len, failed) = self.state.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.rewind();
+        if !failed {
+            // Reading a value doesn't modify the items below it
+            self.state.stack.truncate(len - 1);
+            self.state.stack.push(Item::Value);
+        }
+        self.state.failed = failed;
+    }
+
+    fn release(&mut self) {
+        self.state.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.release();
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        self.inner.error(source)
+    }
+
+    fn error_invalid_name(&self, names: &'static NameMap<usize>) -> Self::Error {
+        self.inner.error_invalid_name(names)
+    }
+
+    fn error_invalid_index(&self, index: usize) -> Self::Error {
+        self.inner.error_invalid_index(index)
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        self.inner.error_missing_item()
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        self.inner.error_extra_item()
+    }
+}
+
+impl<D: ShapeDeserializer> ShapeDeserializer for Checker<D> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        self.state.check("get_number", Kind::Value);
+        let res = self.inner.get_number();
+        self.state.update(&res, |stack, _| {
+            stack.pop();
+        });
+        res
+    }
+
+    fn has_str_keys(&self) -> bool {
+        self.inner.has_str_keys()
+    }
+}
+
+#[test]
+fn test_check_valid() {
+    use crate::{TreeDeserializer, TreeSerializer, Value};
+    let value: (Vec<Option<u32>>, String, [char; 2]) =
+        (vec![Some(1), None], "hi".into(), ['a', 'b']);
+    let mut s = Checker::new(TreeSerializer::new());
+    Value::with(&mut s, |v| v.put(&value)).unwrap();
+    let tree = s.finish().finish();
+    let mut d = Checker::new(TreeDeserializer::new(&tree));
+    let res: (Vec<Option<u32>>, String, [char; 2]) = Value::with(&mut d, |v| v.get()).unwrap();
+    d.finish();
+    assert_eq!(res, value);
+}
+
+#[test]
+#[should_panic(expected = "`push_field` requires an opened struct on top of the stack")]
+fn test_check_invalid() {
+    let mut s = Checker::new(crate::TreeSerializer::new());
+    s.open_str().unwrap();
+    s.push_field("x").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "finished with an opened list left on top of the stack")]
+fn test_check_unclosed() {
+    let mut s = Checker::new(crate::TreeSerializer::new());
+    s.open_list_unsized().unwrap();
+    s.finish();
+}
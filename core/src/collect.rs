@@ -0,0 +1,344 @@
+use crate::deserialize::{ValueKind, NO_CHECKPOINT_ERROR};
+use crate::{Deserializer, NameMap, Number, Outliner, ShapeDeserializer};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// A [`Deserializer`] which wraps another [`Deserializer`], collecting errors instead of failing
+/// on the first one. When reading a field of a struct fails (as reported through
+/// [`crate::Struct::recover`], which derived implementations use), the error is recorded, the
+/// remainder of the field is skipped using [`Deserializer::unwind`], and the struct continues
+/// to be read. The struct itself will still fail, but only after all of its fields have been
+/// checked.
+///
+/// Recovery is only possible if the underlying [`Deserializer`] supports
+/// [`Deserializer::unwind`]. For errors to include the path to the value that caused them, wrap
+/// a [`crate::PathTracker`] in the [`ErrorCollector`], rather than the other way around.
+pub struct ErrorCollector<D: Deserializer> {
+    inner: D,
+    depth: usize,
+    errors: Vec<D::Error>,
+
+    /// The depths of the collections which had a child fail since the most recent recovery at a
+    /// lower depth. Any error that arrives at a lower depth is a consequence of these failures,
+    /// and doesn't need to be recorded.
+    failed: Vec<usize>,
+
+    /// For each checkpoint taken using [`Deserializer::checkpoint`], the depth, number of
+    /// recorded errors, and `failed` at the time it was taken. Errors recorded while reading a
+    /// value speculatively are discarded if the deserializer is rewound.
+    checkpoints: Vec<(usize, usize, Vec<usize>)>,
+}
+
+/// The error returned by a value which could not be read because of errors that were recovered
+/// from, and recorded by an [`ErrorCollector`].
+#[derive(thiserror::Error, Debug)]
+#[error("value contains errors")]
+pub struct RecoveredError;
+
+impl<D: Deserializer> ErrorCollector<D> {
+    /// Wraps the given [`Deserializer`], assuming that its stack consists of a single value.
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            depth: 0,
+            errors: Vec::new(),
+            failed: Vec::new(),
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Gets the errors that have been recorded so far.
+    pub fn errors(&self) -> &[D::Error] {
+        &self.errors
+    }
+
+    /// Gets the underlying [`Deserializer`].
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Given the result of reading the value on this deserializer, returns the value along with
+    /// the underlying [`Deserializer`] if no errors were encountered. Otherwise, returns all of
+    /// the recorded errors, in the order they occurred.
+    pub fn finish<T>(mut self, res: Result<T, D::Error>) -> Result<(T, D), Vec<D::Error>> {
+        match res {
+            Ok(value) if self.errors.is_empty() => Ok((value, self.inner)),
+            Ok(_) => Err(self.errors),
+            Err(err) => {
+                if self.failed.is_empty() {
+                    self.errors.push(err);
+                }
+                Err(self.errors)
+            }
+        }
+    }
+
+    /// Updates the tracked depth after a call which opens a collection.
+    fn open<R>(&mut self, res: Result<R, D::Error>) -> Result<R, D::Error> {
+        let res = res?;
+        self.depth += 1;
+        Ok(res)
+    }
+
+    /// Updates the tracked depth after a call which closes a collection.
+    fn close<R>(&mut self, res: Result<R, D::Error>) -> Result<R, D::Error> {
+        let res = res?;
+        self.depth -= 1;
+        Ok(res)
+    }
+}
+
+impl<D: Deserializer> Outliner for ErrorCollector<D> {
+    type Error = D::Error;
+
+    fn supports_null(&self) -> bool {
+        self.inner.supports_null()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.inner.pop_null()
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_str()
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_str()
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_bytes()
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_bytes()
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let res = self.inner.open_struct(type_name);
+        self.open(res)
+    }
+
+    fn open_struct_with_fields(
+        &mut self,
+        type_name: Option<&'static str>,
+        fields: &'static [&'static str],
+    ) -> Result<(), Self::Error> {
+        let res = self.inner.open_struct_with_fields(type_name, fields);
+        self.open(res)
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.inner.push_field(name)
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.close_struct();
+        self.close(res)
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let res = self.inner.open_tuple(type_name);
+        self.open(res)
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_element()
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.close_tuple();
+        self.close(res)
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_item()
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.close_list();
+        self.close(res)
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_key()
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_value()
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.close_map();
+        self.close(res)
+    }
+}
+
+/// Implements a method of [`Deserializer`] for [`ErrorCollector`] which reads a single value.
+macro_rules! collect_get {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self) -> Result<$ty, Self::Error> {
+            self.inner.$name()
+        }
+    };
+}
+
+impl<D: Deserializer> Deserializer for ErrorCollector<D> {
+    collect_get!(get_bool, bool);
+    collect_get!(get_i8, i8);
+    collect_get!(get_i16, i16);
+    collect_get!(get_i32, i32);
+    collect_get!(get_i64, i64);
+    collect_get!(get_i128, i128);
+    collect_get!(get_u8, u8);
+    collect_get!(get_u16, u16);
+    collect_get!(get_u32, u32);
+    collect_get!(get_u64, u64);
+    collect_get!(get_u128, u128);
+    collect_get!(get_f32, f32);
+    collect_get!(get_f64, f64);
+    collect_get!(get_char, char);
+    collect_get!(next_char, Option<char>);
+    collect_get!(flush_str, Cow<'_, str>);
+    collect_get!(skip_str, ());
+    collect_get!(read_str, Cow<'_, str>);
+    collect_get!(next_bytes_chunk, Option<&[u8]>);
+    collect_get!(flush_bytes, Cow<'_, [u8]>);
+    collect_get!(read_bytes, Cow<'_, [u8]>);
+    collect_get!(check_null, bool);
+
+    fn flush_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        self.inner.flush_name(names)
+    }
+
+    fn get_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        self.inner.get_name(names)
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        self.inner.get_tag(max_index, names)
+    }
+
+    fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = self.inner.open_list();
+        self.open(res)
+    }
+
+    fn next_item(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.next_item()?;
+        if !res {
+            self.depth -= 1;
+        }
+        Ok(res)
+    }
+
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = self.inner.open_map();
+        self.open(res)
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.next_key()?;
+        if !res {
+            self.depth -= 1;
+        }
+        Ok(res)
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        self.inner.peek_kind()
+    }
+
+    fn recovery_depth(&self) -> Option<usize> {
+        Some(self.depth)
+    }
+
+    fn recover(&mut self, depth: usize, err: Self::Error) -> Result<(), Self::Error> {
+        let mut is_consequence = false;
+        while self.failed.last().is_some_and(|d| *d > depth) {
+            self.failed.pop();
+            is_consequence = true;
+        }
+        if !is_consequence {
+            self.errors.push(err);
+        }
+        self.failed.push(depth);
+        if self.unwind(depth) {
+            Ok(())
+        } else {
+            // The error has already been recorded, so only its consequences need to be reported
+            Err(self.inner.error(Box::new(RecoveredError)))
+        }
+    }
+
+    fn unwind(&mut self, depth: usize) -> bool {
+        if self.inner.unwind(depth) {
+            self.depth = depth;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.checkpoint()?;
+        if res {
+            let failed = self.failed.clone();
+            self.checkpoints.push((self.depth, self.errors.len(), failed));
+        }
+        Ok(res)
+    }
+
+    fn rewind(&mut self) {
+        let (depth, num_errors, failed) = self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.rewind();
+        self.depth = depth;
+        self.errors.truncate(num_errors);
+        self.failed = failed;
+    }
+
+    fn release(&mut self) {
+        self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.release();
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        self.inner.error(source)
+    }
+
+    fn error_invalid_name(&self, names: &'static NameMap<usize>) -> Self::Error {
+        self.inner.error_invalid_name(names)
+    }
+
+    fn error_invalid_index(&self, index: usize) -> Self::Error {
+        self.inner.error_invalid_index(index)
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        self.inner.error_missing_item()
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        self.inner.error_extra_item()
+    }
+}
+
+impl<D: ShapeDeserializer> ShapeDeserializer for ErrorCollector<D> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        self.inner.get_number()
+    }
+
+    fn has_str_keys(&self) -> bool {
+        self.inner.has_str_keys()
+    }
+}
@@ -1,7 +1,10 @@
 #[allow(unused_imports)]
 use crate::Serializer;
-use crate::{NameMap, Outliner, Struct, Value};
-use std::borrow::Cow;
+use crate::{FixedNameMap, NameMap, Outliner, Struct, Tuple, Value};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 /// An interface for loading arbitrarily-complex data from a data source. This uses a stack-based
 /// API, described in [`Outliner`].
@@ -26,6 +29,10 @@ pub trait Deserializer: Outliner {
     /// [`i64`].
     fn get_i64(&mut self) -> Result<i64, Self::Error>;
 
+    /// Assuming that the top item on the stack is a value, pops it and interprets it as an
+    /// [`i128`].
+    fn get_i128(&mut self) -> Result<i128, Self::Error>;
+
     /// Assuming that the top item on the stack is a value, pops it and interprets it as a
     /// [`u8`].
     fn get_u8(&mut self) -> Result<u8, Self::Error>;
@@ -42,6 +49,10 @@ pub trait Deserializer: Outliner {
     /// [`u64`].
     fn get_u64(&mut self) -> Result<u64, Self::Error>;
 
+    /// Assuming that the top item on the stack is a value, pops it and interprets it as a
+    /// [`u128`].
+    fn get_u128(&mut self) -> Result<u128, Self::Error>;
+
     /// Assuming that the top item on the stack is a value, pops it and interprets it as an
     /// [`f32`].
     fn get_f32(&mut self) -> Result<f32, Self::Error>;
@@ -61,7 +72,7 @@ pub trait Deserializer: Outliner {
 
     /// Assuming that the top item on the stack is an opened string, reads the remainder of it,
     /// and then pops it. This will return a direct reference to the string data if possible.
-    fn flush_str(&mut self) -> Result<Cow<str>, Self::Error> {
+    fn flush_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
         let mut str = String::new();
         while let Some(ch) = self.next_char()? {
             str.push(ch);
@@ -78,11 +89,33 @@ pub trait Deserializer: Outliner {
 
     /// Assuming that the top item on the stack is a value, pops it and returns it, interpreting
     /// it as a string.
-    fn read_str(&mut self) -> Result<Cow<str>, Self::Error> {
+    fn read_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
         self.open_str()?;
         self.flush_str()
     }
 
+    /// Assuming the top item on the stack is an opened byte string, tries getting the next chunk
+    /// of bytes from it. If one exists, it will be returned (and will be non-empty). Otherwise,
+    /// the byte string will be popped from the stack and this will return `Ok(None)`.
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error>;
+
+    /// Assuming that the top item on the stack is an opened byte string, reads the remainder of
+    /// it, and then pops it. This will return a direct reference to the byte data if possible.
+    fn flush_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = self.next_bytes_chunk()? {
+            bytes.extend_from_slice(chunk);
+        }
+        Ok(Cow::Owned(bytes))
+    }
+
+    /// Assuming that the top item on the stack is a value, pops it and returns it, interpreting
+    /// it as a byte string.
+    fn read_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        self.open_bytes()?;
+        self.flush_bytes()
+    }
+
     /// Assuming that the top item on the stack is an opened string, uses the remainder of it
     /// to perform a lookup into `names`, then pops it.
     fn flush_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
@@ -130,9 +163,80 @@ pub trait Deserializer: Outliner {
     /// Otherwise, the list will be popped from the stack and this will return `false`.
     fn next_item(&mut self) -> Result<bool, Self::Error>;
 
+    /// Assuming that the top item on the stack is a value, asserts that it is a map, popping it
+    /// and pushing an opened map onto the stack.
+    ///
+    /// If known, the number of entries in the map will be returned.
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error>;
+
+    /// Assuming the top item on the stack is an opened map, tries getting the next entry from the
+    /// map. If one exists, its key will be pushed onto the stack as a value and this will return
+    /// `true`. Otherwise, the map will be popped from the stack and this will return `false`.
+    fn next_key(&mut self) -> Result<bool, Self::Error>;
+
+    /// Assuming that the top item on the stack is a value, returns its [`ValueKind`], without
+    /// reading it. This may return [`ValueKind::Unknown`] if the kind can't be determined ahead of
+    /// time, which the default implementation always does. A [`crate::ShapeDeserializer`] never
+    /// returns [`ValueKind::Unknown`].
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        Ok(ValueKind::Unknown)
+    }
+
+    /// If this deserializer is collecting errors instead of failing on the first one (see
+    /// [`crate::ErrorCollector`]), returns the number of opened collections (structs, tuples,
+    /// lists and maps) on the stack. This can later be given to [`Deserializer::recover`] to
+    /// recover from an error that occurs while reading a child of the top collection. Otherwise,
+    /// returns [`None`].
+    fn recovery_depth(&self) -> Option<usize> {
+        None
+    }
+
+    /// Attempts to recover from an error that occurred while reading a child of the collection at
+    /// the given depth, as returned by [`Deserializer::recovery_depth`]. If successful, the error
+    /// is recorded, the remainder of the child is skipped, leaving the collection on top of the
+    /// stack, and this returns `Ok(())`. Otherwise, returns an error which should be propagated.
+    fn recover(&mut self, depth: usize, err: Self::Error) -> Result<(), Self::Error> {
+        let _ = depth;
+        Err(err)
+    }
+
+    /// Attempts to restore the stack after an error, by skipping over everything that has been
+    /// pushed on top of the opened collection at the given depth. The depth counts the opened
+    /// structs, tuples, lists and maps on the stack. Returns `false` if this isn't possible, e.g.
+    /// because the error left the input in an unknown state.
+    fn unwind(&mut self, depth: usize) -> bool {
+        let _ = depth;
+        false
+    }
+
+    /// Assuming that the top item on the stack is a value, takes a checkpoint, allowing the
+    /// value to be read again after an attempt to read it fails (see [`Deserializer::rewind`]).
+    /// Checkpoints may be nested, and each one must be ended by a call to either
+    /// [`Deserializer::rewind`] or [`Deserializer::release`]. Returns `false` if this deserializer
+    /// can't take a checkpoint for the value, in which case neither may be called. The default
+    /// implementation always does so.
+    ///
+    /// Depending on the format, this may require the value to be read into memory up front, so
+    /// errors in the input may be reported here.
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    /// Restores the stack to the state it was in when the most recent checkpoint was taken,
+    /// leaving the value it was taken for on top of the stack, unread, and ends the checkpoint.
+    /// This may be called after an error.
+    fn rewind(&mut self) {
+        unreachable!("{}", NO_CHECKPOINT_ERROR)
+    }
+
+    /// Ends the most recent checkpoint without rewinding to it.
+    fn release(&mut self) {
+        unreachable!("{}", NO_CHECKPOINT_ERROR)
+    }
+
     /// Constructs an error wrapping the given source error. If errors contain position
     /// information, the error will be tagged to the most recently popped item.
-    fn error(&self, source: Box<dyn std::error::Error + Send + Sync>) -> Self::Error;
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error;
 
     /// Constructs an error which says that the previously-read value is not a valid name in
     /// the given [`NameMap`].
@@ -156,15 +260,69 @@ pub trait Deserializer: Outliner {
     fn error_extra_item(&self) -> Self::Error;
 }
 
-/// An [`std::error::Error`] which says that a read name was expected to be in a [`NameMap`],
+/// The error message for a panic that occurs when a checkpoint is rewound or released without
+/// having been taken (see [`Deserializer::checkpoint`]).
+pub const NO_CHECKPOINT_ERROR: &str = "no checkpoint has been taken";
+
+/// Identifies the kind of a value which is about to be read from a [`Deserializer`], as
+/// returned by [`Deserializer::peek_kind`]. This allows [`Deserialize`] implementations to
+/// branch on the contents of the data, e.g. to accept either a number or a string.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ValueKind {
+    /// A `null` literal, or the absence of a value.
+    Null,
+    Bool,
+
+    /// A number which is known to be an integer. For a [`crate::ShapeDeserializer`], this can be
+    /// read using [`crate::ShapeDeserializer::get_number`].
+    Integer,
+
+    /// A number which may have a fractional part. For a [`crate::ShapeDeserializer`], this can be
+    /// read using [`crate::ShapeDeserializer::get_number`].
+    Float,
+
+    /// A string, which can be read using [`Outliner::open_str`].
+    Str,
+
+    /// A byte string, which can be read using [`Outliner::open_bytes`].
+    Bytes,
+
+    /// A collection of named entries, which can be read as a struct or map. For a
+    /// [`crate::ShapeDeserializer`], it can always be read as a map.
+    Struct,
+
+    /// A collection of unnamed items, which can be read as a tuple or list. For a
+    /// [`crate::ShapeDeserializer`], it can always be read as a list.
+    List,
+
+    /// A value whose kind can't be determined without reading it.
+    Unknown,
+}
+
+/// A [`Deserializer`] which can provide direct references to string data in its input, where the
+/// input lives for `'de`. This enables zero-copy deserialization of types such as `&'de str`.
+pub trait BorrowDeserializer<'de>: Deserializer {
+    /// Assuming that the top item on the stack is an opened string, reads the remainder of it,
+    /// and then pops it. This will return a direct reference into the input if possible.
+    fn flush_borrowed_str(&mut self) -> Result<Cow<'de, str>, Self::Error>;
+
+    /// Assuming that the top item on the stack is a value, pops it and returns it, interpreting
+    /// it as a string. This will return a direct reference into the input if possible.
+    fn read_borrowed_str(&mut self) -> Result<Cow<'de, str>, Self::Error> {
+        self.open_str()?;
+        self.flush_borrowed_str()
+    }
+}
+
+/// An [`core::error::Error`] which says that a read name was expected to be in a [`NameMap`],
 /// but wasn't.
 #[derive(Debug)]
 pub struct InvalidNameError {
     pub expected: &'static NameMap<usize>,
 }
 
-impl std::fmt::Display for InvalidNameError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for InvalidNameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str("name is not one of the allowed options (")?;
         let mut is_first = true;
         for (name, _) in self.expected.entries() {
@@ -179,15 +337,50 @@ impl std::fmt::Display for InvalidNameError {
     }
 }
 
-impl std::error::Error for InvalidNameError {}
+impl core::error::Error for InvalidNameError {}
 
-/// An [`std::error::Error`] which says that a read index was invalid.
+/// An [`core::error::Error`] which says that a read index was invalid.
 #[derive(thiserror::Error, Debug)]
 #[error("index {index} is invalid")]
 pub struct InvalidIndexError {
     pub index: usize
 }
 
+/// An [`core::error::Error`] which says that a set contains more than one copy of an item.
+#[derive(thiserror::Error, Debug)]
+#[error("set contains a duplicate item")]
+pub struct DuplicateItemError;
+
+/// An [`core::error::Error`] which says that a map contains more than one entry with the same
+/// key.
+#[derive(thiserror::Error, Debug)]
+#[error("map contains a duplicate key")]
+pub struct DuplicateKeyError;
+
+/// An [`core::error::Error`] which says that a read value is outside of the range supported by
+/// its type.
+#[derive(thiserror::Error, Debug)]
+#[error("value is out of range")]
+pub struct OutOfRangeError;
+
+/// An [`core::error::Error`] which says that a read string is not a valid
+/// [`CharRange`](crate::CharRange), which must have the form `"a-z"`.
+#[derive(thiserror::Error, Debug)]
+#[error("invalid character range syntax")]
+pub struct InvalidCharRangeError;
+
+/// An [`core::error::Error`] which says that a read string is not a valid encoding of a
+/// platform-specific string (e.g. [`std::ffi::OsString`]).
+#[derive(thiserror::Error, Debug)]
+#[error("invalid encoding for platform string")]
+pub struct InvalidOsStrError;
+
+/// An [`core::error::Error`] which says that a string could not be borrowed from the input of a
+/// [`BorrowDeserializer`], e.g. because it contains escape sequences.
+#[derive(thiserror::Error, Debug)]
+#[error("string can't be borrowed from the input")]
+pub struct BorrowError;
+
 /// A type which can be deserialized using a deserializer of type `D` given access to a context
 /// of type `Ctx`.
 pub trait Deserialize<D: Deserializer + ?Sized, Ctx: ?Sized = ()>: Sized {
@@ -200,6 +393,18 @@ pub trait Deserialize<D: Deserializer + ?Sized, Ctx: ?Sized = ()>: Sized {
 
     /// Deserializes a value of this type from the given [`Value`].
     fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error>;
+
+    /// Deserializes a value of this type from the given [`Value`], storing it in `place`. This
+    /// may reuse resources (e.g. allocations) held by the existing value. If an error occurs,
+    /// `place` will be left in a valid, but unspecified, state.
+    fn deserialize_in_place(
+        value: Value<D>,
+        place: &mut Self,
+        context: &mut Ctx,
+    ) -> Result<(), D::Error> {
+        *place = Self::deserialize(value, context)?;
+        Ok(())
+    }
 }
 
 /// A [`Deserialize`] which is deserialized as a struct value. This can be used to inline/flatten
@@ -211,6 +416,17 @@ pub trait DeserializeStruct<D: Deserializer + ?Sized, Ctx: ?Sized = ()>:
 {
     /// Deserializes a value of this type from the given [`Struct`].
     fn deserialize_content(st: &mut Struct<D>, context: &mut Ctx) -> Result<Self, D::Error>;
+
+    /// Deserializes a value of this type from the given [`Struct`], storing it in `place`. See
+    /// [`Deserialize::deserialize_in_place`].
+    fn deserialize_content_in_place(
+        st: &mut Struct<D>,
+        place: &mut Self,
+        context: &mut Ctx,
+    ) -> Result<(), D::Error> {
+        *place = Self::deserialize_content(st, context)?;
+        Ok(())
+    }
 }
 
 /// The standard implementation of [`Deserialize::deserialize`] for a [`DeserializeStruct`].
@@ -218,13 +434,65 @@ pub fn deserialize_struct<T: DeserializeStruct<D, Ctx>, D: Deserializer + ?Sized
     value: Value<D>,
     context: &mut Ctx,
     type_name: Option<&'static str>,
+    fields: Option<&'static [&'static str]>,
 ) -> Result<T, D::Error> {
-    let mut st = value.into_struct(type_name)?;
+    let mut st = value.into_struct(type_name, fields)?;
     let res = T::deserialize_content(&mut st, context)?;
     st.close()?;
     Ok(res)
 }
 
+/// The standard implementation of [`Deserialize::deserialize_in_place`] for a
+/// [`DeserializeStruct`].
+pub fn deserialize_struct_in_place<
+    T: DeserializeStruct<D, Ctx>,
+    D: Deserializer + ?Sized,
+    Ctx: ?Sized,
+>(
+    value: Value<D>,
+    place: &mut T,
+    context: &mut Ctx,
+    type_name: Option<&'static str>,
+    fields: Option<&'static [&'static str]>,
+) -> Result<(), D::Error> {
+    let mut st = value.into_struct(type_name, fields)?;
+    T::deserialize_content_in_place(&mut st, place, context)?;
+    st.close()
+}
+
+/// A [`Deserialize`] which is deserialized as a tuple value. This can be used to inline/flatten
+/// the elements of one tuple into another, or to read several heterogeneous elements of a tuple
+/// in one call.
+///
+/// The implementation of [`Deserialize::deserialize`] should be [`deserialize_tuple`].
+pub trait DeserializeTuple<D: Deserializer + ?Sized, Ctx: ?Sized = ()>:
+    Deserialize<D, Ctx>
+{
+    /// Deserializes a value of this type from the given [`Tuple`].
+    fn deserialize_content(tuple: &mut Tuple<D>, context: &mut Ctx) -> Result<Self, D::Error>;
+}
+
+/// The standard implementation of [`Deserialize::deserialize`] for a [`DeserializeTuple`].
+pub fn deserialize_tuple<T: DeserializeTuple<D, Ctx>, D: Deserializer + ?Sized, Ctx: ?Sized>(
+    value: Value<D>,
+    context: &mut Ctx,
+    type_name: Option<&'static str>,
+) -> Result<T, D::Error> {
+    let mut tuple = value.into_tuple(type_name)?;
+    let res = T::deserialize_content(&mut tuple, context)?;
+    tuple.close()?;
+    Ok(res)
+}
+
+/// A type which can be deserialized using a [`BorrowDeserializer`], borrowing data from its
+/// input for `'de`. Derived implementations use this for fields marked `#[serde(borrow)]`.
+pub trait BorrowDeserialize<'de, D: BorrowDeserializer<'de> + ?Sized, Ctx: ?Sized = ()>:
+    Sized
+{
+    /// Deserializes a [`Value`] using the given deserializer and context.
+    fn deserialize_borrowed(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error>;
+}
+
 impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for bool {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
@@ -260,6 +528,13 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for i64 {
     }
 }
 
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for i128 {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_i128()
+    }
+}
+
 impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for u8 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
@@ -288,6 +563,13 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for u64 {
     }
 }
 
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for u128 {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_u128()
+    }
+}
+
 impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for f32 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
@@ -314,12 +596,78 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for String {
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.get_str().map(Cow::into_owned)
     }
+
+    fn deserialize_in_place(
+        value: Value<D>,
+        place: &mut Self,
+        _: &mut Ctx,
+    ) -> Result<(), D::Error> {
+        let str = value.get_str()?;
+        place.clear();
+        place.push_str(&str);
+        Ok(())
+    }
+}
+
+impl<'de, D: BorrowDeserializer<'de> + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for &'de str {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.validate_with(|value| {
+            Ok(match value.get_borrowed_str()? {
+                Cow::Borrowed(str) => Ok(str),
+                Cow::Owned(_) => Err(BorrowError),
+            })
+        })
+    }
+}
+
+impl<'de, D: BorrowDeserializer<'de> + ?Sized, Ctx: ?Sized> BorrowDeserialize<'de, D, Ctx>
+    for &'de str
+{
+    fn deserialize_borrowed(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        Self::deserialize(value, context)
+    }
+}
+
+impl<'de, D: BorrowDeserializer<'de> + ?Sized, Ctx: ?Sized> BorrowDeserialize<'de, D, Ctx>
+    for Cow<'de, str>
+{
+    fn deserialize_borrowed(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_borrowed_str()
+    }
+}
+
+impl<'de, D: BorrowDeserializer<'de> + ?Sized, Ctx: ?Sized, T: BorrowDeserialize<'de, D, Ctx>>
+    BorrowDeserialize<'de, D, Ctx> for Option<T>
+{
+    fn deserialize_borrowed(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        // Borrowed types are strings, so `null` is always available to represent `None`
+        if value.as_raw().supports_null() {
+            let (d, done) = value.into_raw();
+            if d.check_null()? {
+                *done = true;
+                Ok(None)
+            } else {
+                T::deserialize_borrowed(Value::new(d, done), context).map(Some)
+            }
+        } else {
+            let mut st = value.into_struct(Some("Option"), None)?;
+            let has_value = st.field("has_value")?.get_bool()?;
+            let res = if has_value {
+                Some(T::deserialize_borrowed(st.field("value")?, context)?)
+            } else {
+                None
+            };
+            st.close()?;
+            Ok(res)
+        }
+    }
 }
 
 impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for () {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
-        deserialize_struct(value, context, None)
+        deserialize_struct(value, context, None, Some(&[]))
     }
 }
 
@@ -344,7 +692,7 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<
             }
         } else {
             // Fallback to using a regular struct
-            let mut st = value.into_struct(Some("Option"))?;
+            let mut st = value.into_struct(Some("Option"), None)?;
             let has_value = st.field("has_value")?.get_bool()?;
             let res = if has_value {
                 Some(T::deserialize(st.field("value")?, context)?)
@@ -357,55 +705,78 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized, T0: Deserialize<D, Ctx>, T1: Deserialize<D, Ctx>>
-    Deserialize<D, Ctx> for (T0, T1)
-{
-    const NULLABLE: bool = false;
-    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
-        let mut tuple = value.into_tuple(None)?;
-        let res = (
-            tuple.element()?.get_using(context)?,
-            tuple.element()?.get_using(context)?,
-        );
-        tuple.close()?;
-        Ok(res)
-    }
+macro_rules! impl_tuple {
+    ($($t:ident),+) => {
+        impl<D: Deserializer + ?Sized, Ctx: ?Sized, $($t: Deserialize<D, Ctx>),+>
+            Deserialize<D, Ctx> for ($($t,)+)
+        {
+            const NULLABLE: bool = false;
+            fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+                deserialize_tuple(value, context, None)
+            }
+        }
+
+        impl<D: Deserializer + ?Sized, Ctx: ?Sized, $($t: Deserialize<D, Ctx>),+>
+            DeserializeTuple<D, Ctx> for ($($t,)+)
+        {
+            fn deserialize_content(
+                tuple: &mut Tuple<D>,
+                context: &mut Ctx,
+            ) -> Result<Self, D::Error> {
+                Ok(($(tuple.element()?.get_using::<$t, Ctx>(context)?,)+))
+            }
+        }
+    };
 }
 
-impl<
-        D: Deserializer + ?Sized,
-        Ctx: ?Sized,
-        T0: Deserialize<D, Ctx>,
-        T1: Deserialize<D, Ctx>,
-        T2: Deserialize<D, Ctx>,
-    > Deserialize<D, Ctx> for (T0, T1, T2)
+impl_tuple!(T0);
+impl_tuple!(T0, T1);
+impl_tuple!(T0, T1, T2);
+impl_tuple!(T0, T1, T2, T3);
+impl_tuple!(T0, T1, T2, T3, T4);
+impl_tuple!(T0, T1, T2, T3, T4, T5);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>, const N: usize>
+    Deserialize<D, Ctx> for [T; N]
 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        deserialize_tuple(value, context, None)
+    }
+
+    fn deserialize_in_place(
+        value: Value<D>,
+        place: &mut Self,
+        context: &mut Ctx,
+    ) -> Result<(), D::Error> {
         let mut tuple = value.into_tuple(None)?;
-        let res = (
-            tuple.element()?.get_using(context)?,
-            tuple.element()?.get_using(context)?,
-            tuple.element()?.get_using(context)?,
-        );
-        tuple.close()?;
-        Ok(res)
+        for el in place.iter_mut() {
+            tuple.element()?.get_into_using(el, context)?;
+        }
+        tuple.close()
     }
 }
 
 impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>, const N: usize>
-    Deserialize<D, Ctx> for [T; N]
+    DeserializeTuple<D, Ctx> for [T; N]
 {
-    const NULLABLE: bool = false;
-    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
-        let mut tuple = value.into_tuple(None)?;
+    fn deserialize_content(tuple: &mut Tuple<D>, context: &mut Ctx) -> Result<Self, D::Error> {
         // TODO: Use `try_from_fn` when available:
         // https://github.com/rust-lang/rust/issues/89379
         let mut res: [Option<T>; N] = core::array::from_fn(|_| None);
         for el in res.iter_mut() {
             *el = Some(tuple.element()?.get_using(context)?);
         }
-        tuple.close()?;
         Ok(res.map(|x| x.unwrap()))
     }
 }
@@ -420,9 +791,310 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<
         }
         Ok(res)
     }
+
+    fn deserialize_in_place(
+        value: Value<D>,
+        place: &mut Self,
+        context: &mut Ctx,
+    ) -> Result<(), D::Error> {
+        let mut list = value.into_list()?;
+        let mut len = 0;
+        while let Some(item) = list.next()? {
+            if let Some(existing) = place.get_mut(len) {
+                item.get_into_using(existing, context)?;
+            } else {
+                place.push(item.get_using(context)?);
+            }
+            len += 1;
+        }
+        place.truncate(len);
+        Ok(())
+    }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroI8 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for Box<[T]>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(value, context).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for alloc::collections::VecDeque<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut list = value.into_list()?;
+        let mut res = alloc::collections::VecDeque::with_capacity(list.rem_len().unwrap_or(0));
+        while let Some(item) = list.next()? {
+            res.push_back(item.get_using(context)?);
+        }
+        Ok(res)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for alloc::collections::LinkedList<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut res = alloc::collections::LinkedList::new();
+        let mut list = value.into_list()?;
+        while let Some(item) = list.next()? {
+            res.push_back(item.get_using(context)?);
+        }
+        Ok(res)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx> + Ord> Deserialize<D, Ctx>
+    for alloc::collections::BinaryHeap<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut list = value.into_list()?;
+        let mut res = alloc::collections::BinaryHeap::with_capacity(list.rem_len().unwrap_or(0));
+        while let Some(item) = list.next()? {
+            res.push(item.get_using(context)?);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<
+        D: Deserializer + ?Sized,
+        Ctx: ?Sized,
+        T: Deserialize<D, Ctx> + Eq + core::hash::Hash,
+        H: core::hash::BuildHasher + Default,
+    > Deserialize<D, Ctx> for std::collections::HashSet<T, H>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut list = value.into_list()?;
+        let mut res = std::collections::HashSet::with_capacity_and_hasher(
+            list.rem_len().unwrap_or(0),
+            H::default(),
+        );
+        while let Some(item) = list.next()? {
+            item.validate_with(|item| {
+                Ok(if res.insert(item.get_using(context)?) {
+                    Ok(())
+                } else {
+                    Err(DuplicateItemError)
+                })
+            })?;
+        }
+        Ok(res)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx> + Ord> Deserialize<D, Ctx>
+    for alloc::collections::BTreeSet<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut res = alloc::collections::BTreeSet::new();
+        let mut list = value.into_list()?;
+        while let Some(item) = list.next()? {
+            item.validate_with(|item| {
+                Ok(if res.insert(item.get_using(context)?) {
+                    Ok(())
+                } else {
+                    Err(DuplicateItemError)
+                })
+            })?;
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<
+        D: Deserializer + ?Sized,
+        Ctx: ?Sized,
+        K: Deserialize<D, Ctx> + Eq + core::hash::Hash,
+        V: Deserialize<D, Ctx>,
+        H: core::hash::BuildHasher + Default,
+    > Deserialize<D, Ctx> for std::collections::HashMap<K, V, H>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut res = std::collections::HashMap::default();
+        let mut map = value.into_map()?;
+        while let Some(key) = map.next_key()? {
+            let key = key.validate_with(|key| {
+                let key = key.get_using(context)?;
+                Ok(if res.contains_key(&key) {
+                    Err(DuplicateKeyError)
+                } else {
+                    Ok(key)
+                })
+            })?;
+            let value = map.value()?.get_using(context)?;
+            res.insert(key, value);
+        }
+        Ok(res)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, K: Deserialize<D, Ctx> + Ord, V: Deserialize<D, Ctx>>
+    Deserialize<D, Ctx> for alloc::collections::BTreeMap<K, V>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let mut res = alloc::collections::BTreeMap::new();
+        let mut map = value.into_map()?;
+        while let Some(key) = map.next_key()? {
+            let key = key.validate_with(|key| {
+                let key = key.get_using(context)?;
+                Ok(if res.contains_key(&key) {
+                    Err(DuplicateKeyError)
+                } else {
+                    Ok(key)
+                })
+            })?;
+            let value = map.value()?.get_using(context)?;
+            res.insert(key, value);
+        }
+        Ok(res)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx> for Box<T> {
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(Box::new)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for alloc::rc::Rc<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(alloc::rc::Rc::new)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for alloc::sync::Arc<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(alloc::sync::Arc::new)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for Box<str> {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        Ok(value.get_str()?.into())
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for alloc::rc::Rc<str> {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        Ok(value.get_str()?.into())
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for alloc::sync::Arc<str> {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        Ok(value.get_str()?.into())
+    }
+}
+
+impl<'a, D: Deserializer + ?Sized, Ctx: ?Sized, T: ToOwned + ?Sized> Deserialize<D, Ctx>
+    for Cow<'a, T>
+where
+    T::Owned: Deserialize<D, Ctx>,
+{
+    const NULLABLE: bool = T::Owned::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::Owned::deserialize(value, context).map(Cow::Owned)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::cell::Cell<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(core::cell::Cell::new)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::cell::RefCell<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(core::cell::RefCell::new)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for std::sync::Mutex<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(std::sync::Mutex::new)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for std::sync::RwLock<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(std::sync::RwLock::new)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::cmp::Reverse<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(core::cmp::Reverse)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::num::Wrapping<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(core::num::Wrapping)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::num::Saturating<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        T::deserialize(value, context).map(core::num::Saturating)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: ?Sized> Deserialize<D, Ctx>
+    for core::marker::PhantomData<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        <()>::deserialize(value, context).map(|()| core::marker::PhantomData)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroI8 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -431,7 +1103,7 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroI16 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroI16 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -440,7 +1112,7 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroI32 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroI32 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -449,7 +1121,7 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroI64 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroI64 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -458,7 +1130,16 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroU8 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroI128 {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.validate_with(|value| {
+            Ok(value.get_i128()?.try_into())
+        })
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroU8 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -467,7 +1148,7 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroU16 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroU16 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -476,7 +1157,7 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroU32 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroU32 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
@@ -485,11 +1166,220 @@ impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::No
     }
 }
 
-impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::num::NonZeroU64 {
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroU64 {
     const NULLABLE: bool = false;
     fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
         value.validate_with(|value| {
             Ok(value.get_u64()?.try_into())
         })
     }
-}
\ No newline at end of file
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::num::NonZeroU128 {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.validate_with(|value| {
+            Ok(value.get_u128()?.try_into())
+        })
+    }
+}
+
+/// Deserializes the sub-second component of a time value, ensuring it is in the valid range.
+fn get_nanos<D: Deserializer + ?Sized>(value: Value<D>) -> Result<u32, D::Error> {
+    value.validate_with(|value| {
+        let nanos = value.get_u32()?;
+        Ok(if nanos < 1_000_000_000 {
+            Ok(nanos)
+        } else {
+            Err(OutOfRangeError)
+        })
+    })
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::time::Duration {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        deserialize_struct(value, context, Some("Duration"), Some(&["secs", "nanos"]))
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> DeserializeStruct<D, Ctx> for core::time::Duration {
+    fn deserialize_content(st: &mut Struct<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        let secs = st.field("secs")?.get_u64()?;
+        let nanos = get_nanos(st.field("nanos")?)?;
+        Ok(Self::new(secs, nanos))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for std::time::SystemTime {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.validate_with(|value| {
+            let mut st = value.into_struct(Some("SystemTime"), Some(&["secs", "nanos"]))?;
+            let secs = st.field("secs")?.get_i64()?;
+            let nanos = get_nanos(st.field("nanos")?)?;
+            st.close()?;
+            let epoch = std::time::UNIX_EPOCH;
+            let res = if secs >= 0 {
+                epoch.checked_add(core::time::Duration::new(secs as u64, nanos))
+            } else {
+                epoch
+                    .checked_sub(core::time::Duration::from_secs(secs.unsigned_abs()))
+                    .and_then(|time| {
+                        time.checked_add(core::time::Duration::from_nanos(nanos.into()))
+                    })
+            };
+            Ok(res.ok_or(OutOfRangeError))
+        })
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::ops::Range<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        deserialize_struct(value, context, Some("Range"), Some(&["start", "end"]))
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> DeserializeStruct<D, Ctx>
+    for core::ops::Range<T>
+{
+    fn deserialize_content(st: &mut Struct<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let start = st.field("start")?.get_using(context)?;
+        let end = st.field("end")?.get_using(context)?;
+        Ok(start..end)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::ops::RangeInclusive<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        deserialize_struct(value, context, Some("RangeInclusive"), Some(&["start", "end"]))
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> DeserializeStruct<D, Ctx>
+    for core::ops::RangeInclusive<T>
+{
+    fn deserialize_content(st: &mut Struct<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        let start = st.field("start")?.get_using(context)?;
+        let end = st.field("end")?.get_using(context)?;
+        Ok(start..=end)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> Deserialize<D, Ctx>
+    for core::ops::Bound<T>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        deserialize_struct(value, context, Some("Bound"), None)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>> DeserializeStruct<D, Ctx>
+    for core::ops::Bound<T>
+{
+    fn deserialize_content(st: &mut Struct<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        const NAMES: &NameMap<usize> =
+            FixedNameMap::new([("Included", 0), ("Excluded", 1), ("Unbounded", 2)]).unfix();
+        let (de, done_flag) = st.field("type")?.into_raw();
+        let index = de.get_tag(2, NAMES)?;
+        *done_flag = true;
+        Ok(match index {
+            0 => Self::Included(st.field("value")?.get_using(context)?),
+            1 => Self::Excluded(st.field("value")?.get_using(context)?),
+            2 => Self::Unbounded,
+            index => return Err(de.error_invalid_index(index)),
+        })
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>, E: Deserialize<D, Ctx>>
+    Deserialize<D, Ctx> for Result<T, E>
+{
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        deserialize_struct(value, context, Some("Result"), None)
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized, T: Deserialize<D, Ctx>, E: Deserialize<D, Ctx>>
+    DeserializeStruct<D, Ctx> for Result<T, E>
+{
+    fn deserialize_content(st: &mut Struct<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        const NAMES: &NameMap<usize> = FixedNameMap::new([("Ok", 0), ("Err", 1)]).unfix();
+        let (de, done_flag) = st.field("type")?.into_raw();
+        let index = de.get_tag(1, NAMES)?;
+        *done_flag = true;
+        Ok(match index {
+            0 => Ok(st.field("value")?.get_using(context)?),
+            1 => Err(st.field("value")?.get_using(context)?),
+            index => return Err(de.error_invalid_index(index)),
+        })
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::cmp::Ordering {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        const NAMES: &NameMap<usize> =
+            FixedNameMap::new([("Less", 0), ("Equal", 1), ("Greater", 2)]).unfix();
+        let (de, done_flag) = value.into_raw();
+        let index = de.get_tag(2, NAMES)?;
+        *done_flag = true;
+        match index {
+            0 => Ok(core::cmp::Ordering::Less),
+            1 => Ok(core::cmp::Ordering::Equal),
+            2 => Ok(core::cmp::Ordering::Greater),
+            index => Err(de.error_invalid_index(index)),
+        }
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::net::IpAddr {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::net::Ipv4Addr {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::net::Ipv6Addr {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::net::SocketAddr {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::net::SocketAddrV4 {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for core::net::SocketAddrV6 {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.get_parsed()
+    }
+}
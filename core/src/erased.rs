@@ -0,0 +1,351 @@
+use crate::{Deserializer, NameMap, Number, Outliner, Serialize, Serializer};
+use crate::{ShapeDeserializer, Value, ValueKind};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+
+/// The error type for [`ErasedSerializer`] and [`ErasedDeserializer`]. This wraps the error of
+/// the underlying [`Outliner`], which can be recovered using [`ErasedError::downcast`].
+#[derive(thiserror::Error, Debug)]
+#[error(transparent)]
+pub struct ErasedError(Box<dyn core::error::Error + Send + Sync>);
+
+impl ErasedError {
+    /// Wraps the given error.
+    pub fn new(source: impl core::error::Error + Send + Sync + 'static) -> Self {
+        Self(Box::new(source))
+    }
+
+    /// Attempts to recover the wrapped error as a concrete type.
+    pub fn downcast<E: core::error::Error + 'static>(self) -> Result<E, Self> {
+        self.0.downcast().map(|err| *err).map_err(Self)
+    }
+
+    /// Gets the wrapped error.
+    pub fn into_inner(self) -> Box<dyn core::error::Error + Send + Sync> {
+        self.0
+    }
+}
+
+/// An object-safe [`Serializer`] with a type-erased error. `dyn ErasedSerializer` implements
+/// [`Serializer`], so any [`Serialize`] implementation which is generic over its serializer can
+/// be used with it. A concrete [`Serializer`] can be converted to this form using [`Erase`].
+pub trait ErasedSerializer: Serializer<Error = ErasedError> {}
+
+impl<S: Serializer<Error = ErasedError> + ?Sized> ErasedSerializer for S {}
+
+/// An object-safe [`Deserializer`] with a type-erased error. `dyn ErasedDeserializer`
+/// implements [`Deserializer`], so any [`crate::Deserialize`] implementation which is generic
+/// over its deserializer can be used with it. A concrete [`Deserializer`] can be converted to
+/// this form using [`Erase`].
+pub trait ErasedDeserializer: Deserializer<Error = ErasedError> {}
+
+impl<D: Deserializer<Error = ErasedError> + ?Sized> ErasedDeserializer for D {}
+
+/// An object-safe counterpart to [`Serialize`]. This is automatically implemented for every
+/// type which can be serialized using a `dyn ErasedSerializer`, and allows values of different
+/// types to be stored and serialized as `dyn ErasedSerialize`.
+pub trait ErasedSerialize {
+    /// Serializes this value using an [`ErasedSerializer`].
+    fn erased_serialize(&self, value: Value<dyn ErasedSerializer + '_>) -> Result<(), ErasedError>;
+}
+
+impl<T: for<'a> Serialize<dyn ErasedSerializer + 'a>> ErasedSerialize for T {
+    fn erased_serialize(&self, value: Value<dyn ErasedSerializer + '_>) -> Result<(), ErasedError> {
+        self.serialize(value, &mut ())
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for dyn ErasedSerialize + '_
+where
+    S::Error: Send + Sync + 'static,
+{
+    const NULLABLE: bool = true;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        let (s, done_flag) = value.into_raw();
+        let mut s = Erase::new(s);
+        match self.erased_serialize(Value::new(&mut s, done_flag)) {
+            Ok(()) => Ok(()),
+            Err(err) => match err.downcast::<S::Error>() {
+                Ok(err) => Err(err),
+                Err(err) => Err(s.inner.error(err.into_inner())),
+            },
+        }
+    }
+}
+
+/// Wraps an [`Outliner`] to give it a type-erased error, converting it into an
+/// [`ErasedSerializer`] or [`ErasedDeserializer`].
+pub struct Erase<'a, O: Outliner + ?Sized> {
+    inner: &'a mut O,
+    map_error: fn(O::Error) -> ErasedError,
+}
+
+impl<'a, O: Outliner + ?Sized> Erase<'a, O> {
+    /// Wraps the given [`Outliner`], boxing its errors.
+    pub fn new(inner: &'a mut O) -> Self
+    where
+        O::Error: Send + Sync + 'static,
+    {
+        Self::with_map_error(inner, ErasedError::new)
+    }
+
+    /// Wraps the given [`Outliner`], using the given function to convert its errors. This is
+    /// needed when the errors of the [`Outliner`] aren't `'static`, e.g. because they borrow
+    /// from the input.
+    pub fn with_map_error(inner: &'a mut O, map_error: fn(O::Error) -> ErasedError) -> Self {
+        Self { inner, map_error }
+    }
+}
+
+impl<O: Outliner + ?Sized> Outliner for Erase<'_, O> {
+    type Error = ErasedError;
+
+    fn supports_null(&self) -> bool {
+        self.inner.supports_null()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.inner.pop_null().map_err(self.map_error)
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_str().map_err(self.map_error)
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_str().map_err(self.map_error)
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_bytes().map_err(self.map_error)
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_bytes().map_err(self.map_error)
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        self.inner.open_struct(type_name).map_err(self.map_error)
+    }
+
+    fn open_struct_with_fields(
+        &mut self,
+        type_name: Option<&'static str>,
+        fields: &'static [&'static str],
+    ) -> Result<(), Self::Error> {
+        let res = self.inner.open_struct_with_fields(type_name, fields);
+        res.map_err(self.map_error)
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.inner.push_field(name).map_err(self.map_error)
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_struct().map_err(self.map_error)
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        self.inner.open_tuple(type_name).map_err(self.map_error)
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_element().map_err(self.map_error)
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_tuple().map_err(self.map_error)
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_item().map_err(self.map_error)
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_list().map_err(self.map_error)
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_key().map_err(self.map_error)
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_value().map_err(self.map_error)
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_map().map_err(self.map_error)
+    }
+}
+
+/// Implements a method of [`Serializer`] for [`Erase`] which writes a single value.
+macro_rules! erase_put {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self, value: $ty) -> Result<(), Self::Error> {
+            self.inner.$name(value).map_err(self.map_error)
+        }
+    };
+}
+
+impl<S: Serializer + ?Sized> Serializer for Erase<'_, S> {
+    erase_put!(put_bool, bool);
+    erase_put!(put_i8, i8);
+    erase_put!(put_i16, i16);
+    erase_put!(put_i32, i32);
+    erase_put!(put_i64, i64);
+    erase_put!(put_i128, i128);
+    erase_put!(put_u8, u8);
+    erase_put!(put_u16, u16);
+    erase_put!(put_u32, u32);
+    erase_put!(put_u64, u64);
+    erase_put!(put_u128, u128);
+    erase_put!(put_f32, f32);
+    erase_put!(put_f64, f64);
+    erase_put!(put_char, char);
+    erase_put!(append_char, char);
+    erase_put!(append_str, &str);
+    erase_put!(put_str, &str);
+    erase_put!(append_bytes, &[u8]);
+    erase_put!(put_bytes, &[u8]);
+
+    fn put_tag(
+        &mut self,
+        max_index: usize,
+        index: usize,
+        name: Option<&'static str>,
+    ) -> Result<(), Self::Error> {
+        self.inner.put_tag(max_index, index, name).map_err(self.map_error)
+    }
+
+    fn open_list_sized(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.inner.open_list_sized(len).map_err(self.map_error)
+    }
+
+    fn open_map_sized(&mut self, len: usize, str_keys: bool) -> Result<(), Self::Error> {
+        self.inner.open_map_sized(len, str_keys).map_err(self.map_error)
+    }
+
+    fn supports_unsized(&self) -> bool {
+        self.inner.supports_unsized()
+    }
+
+    fn open_list_unsized(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_list_unsized().map_err(self.map_error)
+    }
+
+    fn open_map_unsized(&mut self, str_keys: bool) -> Result<(), Self::Error> {
+        self.inner.open_map_unsized(str_keys).map_err(self.map_error)
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        (self.map_error)(self.inner.error(source))
+    }
+}
+
+/// Implements a method of [`Deserializer`] for [`Erase`] which reads a single value.
+macro_rules! erase_get {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self) -> Result<$ty, Self::Error> {
+            self.inner.$name().map_err(self.map_error)
+        }
+    };
+}
+
+impl<D: Deserializer + ?Sized> Deserializer for Erase<'_, D> {
+    erase_get!(get_bool, bool);
+    erase_get!(get_i8, i8);
+    erase_get!(get_i16, i16);
+    erase_get!(get_i32, i32);
+    erase_get!(get_i64, i64);
+    erase_get!(get_i128, i128);
+    erase_get!(get_u8, u8);
+    erase_get!(get_u16, u16);
+    erase_get!(get_u32, u32);
+    erase_get!(get_u64, u64);
+    erase_get!(get_u128, u128);
+    erase_get!(get_f32, f32);
+    erase_get!(get_f64, f64);
+    erase_get!(get_char, char);
+    erase_get!(next_char, Option<char>);
+    erase_get!(flush_str, Cow<'_, str>);
+    erase_get!(skip_str, ());
+    erase_get!(read_str, Cow<'_, str>);
+    erase_get!(next_bytes_chunk, Option<&[u8]>);
+    erase_get!(flush_bytes, Cow<'_, [u8]>);
+    erase_get!(read_bytes, Cow<'_, [u8]>);
+    erase_get!(check_null, bool);
+    erase_get!(open_list, Option<usize>);
+    erase_get!(next_item, bool);
+    erase_get!(open_map, Option<usize>);
+    erase_get!(next_key, bool);
+
+    fn flush_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        self.inner.flush_name(names).map_err(self.map_error)
+    }
+
+    fn get_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        self.inner.get_name(names).map_err(self.map_error)
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        self.inner.get_tag(max_index, names).map_err(self.map_error)
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        self.inner.peek_kind().map_err(self.map_error)
+    }
+
+    fn unwind(&mut self, depth: usize) -> bool {
+        self.inner.unwind(depth)
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        self.inner.checkpoint().map_err(self.map_error)
+    }
+
+    fn rewind(&mut self) {
+        self.inner.rewind()
+    }
+
+    fn release(&mut self) {
+        self.inner.release()
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        (self.map_error)(self.inner.error(source))
+    }
+
+    fn error_invalid_name(&self, names: &'static NameMap<usize>) -> Self::Error {
+        (self.map_error)(self.inner.error_invalid_name(names))
+    }
+
+    fn error_invalid_index(&self, index: usize) -> Self::Error {
+        (self.map_error)(self.inner.error_invalid_index(index))
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        (self.map_error)(self.inner.error_missing_item())
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        (self.map_error)(self.inner.error_extra_item())
+    }
+}
+
+impl<D: ShapeDeserializer + ?Sized> ShapeDeserializer for Erase<'_, D> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        self.inner.get_number().map_err(self.map_error)
+    }
+
+    fn has_str_keys(&self) -> bool {
+        self.inner.has_str_keys()
+    }
+}
@@ -1,7 +1,11 @@
-use crate::deserialize::{Deserialize, DeserializeStruct, Deserializer};
-use crate::serialize::{Serialize, SerializeStruct, Serializer};
-use crate::{NameMap, Outliner};
-use std::borrow::Cow;
+use crate::deserialize::{BorrowDeserialize, BorrowDeserializer};
+use crate::deserialize::{Deserialize, DeserializeStruct, DeserializeTuple, Deserializer};
+use crate::deserialize::ValueKind;
+use crate::serialize::{Serialize, SerializeStruct, SerializeTuple, Serializer};
+use crate::{NameMap, Outliner, RecoveredError};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 /// A wrapper over an [`Outliner`] which has a value at the top of its stack.
 /// 
@@ -37,6 +41,12 @@ impl<'a, O: Outliner + ?Sized> Value<'a, O> {
         self.source
     }
 
+    /// Indicates whether the underlying serialization format is human-readable. See
+    /// [`Outliner::is_human_readable`].
+    pub fn is_human_readable(&self) -> bool {
+        self.source.is_human_readable()
+    }
+
     /// Gets the underlying [`Outliner`] and `done_flag` for this [`Value`]. It is the caller's
     /// responsibility to uphold the invariants normally kept by the [`Value`]: `done_flag`
     /// should be set to `true` when the value at the top of the stack has been popped, and no
@@ -45,10 +55,18 @@ impl<'a, O: Outliner + ?Sized> Value<'a, O> {
         (self.source, self.done_flag)
     }
 
-    /// Asserts that this value is a struct.
-    pub fn into_struct(self, type_name: Option<&'static str>) -> Result<Struct<'a, O>, O::Error> {
+    /// Asserts that this value is a struct. If all of its fields are known up front, their names
+    /// should be given in `fields` (see [`Outliner::open_struct_with_fields`]).
+    pub fn into_struct(
+        self,
+        type_name: Option<&'static str>,
+        fields: Option<&'static [&'static str]>,
+    ) -> Result<Struct<'a, O>, O::Error> {
         assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
-        self.source.open_struct(type_name)?;
+        match fields {
+            Some(fields) => self.source.open_struct_with_fields(type_name, fields)?,
+            None => self.source.open_struct(type_name)?,
+        }
         Ok(Struct::new(self.source, self.done_flag))
     }
 
@@ -115,6 +133,14 @@ impl<'a, S: Serializer + ?Sized> Value<'a, S> {
         Ok(())
     }
 
+    /// Assigns this value to the given [`i128`].
+    pub fn put_i128(self, value: i128) -> Result<(), S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.put_i128(value)?;
+        *self.done_flag = true;
+        Ok(())
+    }
+
     /// Assigns this value to the given [`u8`].
     pub fn put_u8(self, value: u8) -> Result<(), S::Error> {
         assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
@@ -147,6 +173,14 @@ impl<'a, S: Serializer + ?Sized> Value<'a, S> {
         Ok(())
     }
 
+    /// Assigns this value to the given [`u128`].
+    pub fn put_u128(self, value: u128) -> Result<(), S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.put_u128(value)?;
+        *self.done_flag = true;
+        Ok(())
+    }
+
     /// Assigns this value to the given [`f32`].
     pub fn put_f32(self, value: f32) -> Result<(), S::Error> {
         assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
@@ -179,6 +213,35 @@ impl<'a, S: Serializer + ?Sized> Value<'a, S> {
         Ok(())
     }
 
+    /// Assigns this value to the given byte string.
+    pub fn put_bytes(self, value: &[u8]) -> Result<(), S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.put_bytes(value)?;
+        *self.done_flag = true;
+        Ok(())
+    }
+
+    /// Assigns this value to a string consisting of the [`Display`](core::fmt::Display) output
+    /// of the given value. The output is written as it is produced, without being collected into
+    /// an intermediate [`String`](alloc::string::String).
+    pub fn put_display(self, value: &(impl core::fmt::Display + ?Sized)) -> Result<(), S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.open_str()?;
+        let mut writer = StrWriter {
+            source: &mut *self.source,
+            error: None,
+        };
+        if core::fmt::write(&mut writer, format_args!("{}", value)).is_err() {
+            return Err(match writer.error {
+                Some(err) => err,
+                None => self.source.error(Box::new(core::fmt::Error)),
+            });
+        }
+        self.source.close_str()?;
+        *self.done_flag = true;
+        Ok(())
+    }
+
     /// Assigns this value to an enum tag.
     pub fn put_tag(
         self,
@@ -198,6 +261,193 @@ impl<'a, S: Serializer + ?Sized> Value<'a, S> {
         self.source.open_list_sized(len)?;
         Ok(List::new(self.source, self.done_flag, Some(len)))
     }
+
+    /// Asserts that this value is a map with the given number of entries. `str_keys` indicates
+    /// whether every key will be written as a string (see [`Serializer::open_map_sized`]).
+    pub fn into_map_sized(self, len: usize, str_keys: bool) -> Result<Map<'a, S>, S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.open_map_sized(len, str_keys)?;
+        Ok(Map::new(self.source, self.done_flag, Some(len)))
+    }
+
+    /// Asserts that this value is a list with an unspecified number of items. This may only be
+    /// called if [`Serializer::supports_unsized`] returns `true`.
+    pub fn into_list_unsized(self) -> Result<List<'a, S>, S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.open_list_unsized()?;
+        Ok(List::new(self.source, self.done_flag, None))
+    }
+
+    /// Asserts that this value is a map with an unspecified number of entries. This may only be
+    /// called if [`Serializer::supports_unsized`] returns `true`. `str_keys` is as in
+    /// [`Value::into_map_sized`].
+    pub fn into_map_unsized(self, str_keys: bool) -> Result<Map<'a, S>, S::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.open_map_unsized(str_keys)?;
+        Ok(Map::new(self.source, self.done_flag, None))
+    }
+
+    /// Assigns this value to a list consisting of the items of the given iterator, without
+    /// collecting them first. The list is streamed if [`Serializer::supports_unsized`] returns
+    /// `true`, and buffered if not. For iterators whose length is known exactly, prefer
+    /// [`Value::put_exact_iter`], which always writes the length up front.
+    pub fn put_iter<I: IntoIterator>(self, iter: I) -> Result<(), S::Error>
+    where
+        I::Item: Serialize<S>,
+    {
+        self.put_iter_using(iter, &mut ())
+    }
+
+    /// Assigns this value to a list consisting of the items of the given iterator. See
+    /// [`Value::put_iter`].
+    pub fn put_iter_using<I: IntoIterator, Ctx: ?Sized>(
+        self,
+        iter: I,
+        context: &mut Ctx,
+    ) -> Result<(), S::Error>
+    where
+        I::Item: Serialize<S, Ctx>,
+    {
+        if self.source.supports_unsized() {
+            put_items(self.into_list_unsized()?, iter, context)
+        } else {
+            let items = iter.into_iter().collect::<Vec<_>>();
+            put_items(self.into_list_sized(items.len())?, items, context)
+        }
+    }
+
+    /// Assigns this value to a list consisting of the items of the given [`ExactSizeIterator`],
+    /// writing its length up front.
+    pub fn put_exact_iter<I: IntoIterator>(self, iter: I) -> Result<(), S::Error>
+    where
+        I::IntoIter: ExactSizeIterator,
+        I::Item: Serialize<S>,
+    {
+        self.put_exact_iter_using(iter, &mut ())
+    }
+
+    /// Assigns this value to a list consisting of the items of the given [`ExactSizeIterator`].
+    /// See [`Value::put_exact_iter`].
+    pub fn put_exact_iter_using<I: IntoIterator, Ctx: ?Sized>(
+        self,
+        iter: I,
+        context: &mut Ctx,
+    ) -> Result<(), S::Error>
+    where
+        I::IntoIter: ExactSizeIterator,
+        I::Item: Serialize<S, Ctx>,
+    {
+        let iter = iter.into_iter();
+        put_items(self.into_list_sized(iter.len())?, iter, context)
+    }
+
+    /// Assigns this value to a map consisting of the key-value pairs of the given iterator,
+    /// without collecting them first. As with [`Value::put_iter`], the map is streamed if
+    /// [`Serializer::supports_unsized`] returns `true`, and buffered if not.
+    pub fn put_map_iter<I: IntoIterator<Item = (K, V)>, K: Serialize<S>, V: Serialize<S>>(
+        self,
+        iter: I,
+    ) -> Result<(), S::Error> {
+        self.put_map_iter_using(iter, &mut ())
+    }
+
+    /// Assigns this value to a map consisting of the key-value pairs of the given iterator. See
+    /// [`Value::put_map_iter`].
+    pub fn put_map_iter_using<I, K, V, Ctx: ?Sized>(
+        self,
+        iter: I,
+        context: &mut Ctx,
+    ) -> Result<(), S::Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Serialize<S, Ctx>,
+        V: Serialize<S, Ctx>,
+    {
+        if self.source.supports_unsized() {
+            put_entries(self.into_map_unsized(K::IS_STR)?, iter, context)
+        } else {
+            let entries = iter.into_iter().collect::<Vec<_>>();
+            put_entries(self.into_map_sized(entries.len(), K::IS_STR)?, entries, context)
+        }
+    }
+
+    /// Assigns this value to a map consisting of the key-value pairs of the given
+    /// [`ExactSizeIterator`], writing its length up front.
+    pub fn put_exact_map_iter<I, K: Serialize<S>, V: Serialize<S>>(
+        self,
+        iter: I,
+    ) -> Result<(), S::Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.put_exact_map_iter_using(iter, &mut ())
+    }
+
+    /// Assigns this value to a map consisting of the key-value pairs of the given
+    /// [`ExactSizeIterator`]. See [`Value::put_exact_map_iter`].
+    pub fn put_exact_map_iter_using<I, K, V, Ctx: ?Sized>(
+        self,
+        iter: I,
+        context: &mut Ctx,
+    ) -> Result<(), S::Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        I::IntoIter: ExactSizeIterator,
+        K: Serialize<S, Ctx>,
+        V: Serialize<S, Ctx>,
+    {
+        let iter = iter.into_iter();
+        put_entries(self.into_map_sized(iter.len(), K::IS_STR)?, iter, context)
+    }
+}
+
+/// Writes the given items to a [`List`] and closes it.
+fn put_items<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>>(
+    mut list: List<S>,
+    items: impl IntoIterator<Item = T>,
+    context: &mut Ctx,
+) -> Result<(), S::Error> {
+    for item in items {
+        list.push()?.put_using(&item, context)?;
+    }
+    list.close()
+}
+
+/// Writes the given entries to a [`Map`] and closes it.
+fn put_entries<S: Serializer + ?Sized, Ctx: ?Sized, K: Serialize<S, Ctx>, V: Serialize<S, Ctx>>(
+    mut map: Map<S>,
+    entries: impl IntoIterator<Item = (K, V)>,
+    context: &mut Ctx,
+) -> Result<(), S::Error> {
+    for (k, v) in entries {
+        map.key()?.put_using(&k, context)?;
+        map.value()?.put_using(&v, context)?;
+    }
+    map.close()
+}
+
+/// Adapts a [`Serializer`] which has an opened string at the top of its stack into a
+/// [`core::fmt::Write`], holding on to the error that caused writing to fail, if any.
+struct StrWriter<'a, S: Serializer + ?Sized> {
+    source: &'a mut S,
+    error: Option<S::Error>,
+}
+
+impl<S: Serializer + ?Sized> core::fmt::Write for StrWriter<'_, S> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.source.append_str(s).map_err(|err| {
+            self.error = Some(err);
+            core::fmt::Error
+        })
+    }
+
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.source.append_char(c).map_err(|err| {
+            self.error = Some(err);
+            core::fmt::Error
+        })
+    }
 }
 
 impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
@@ -214,6 +464,43 @@ impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
         T::deserialize(self, context)
     }
 
+    /// Interprets this value as the given type, storing it in `place`. This may reuse resources
+    /// held by the existing value.
+    pub fn get_into<T: Deserialize<D>>(self, place: &mut T) -> Result<(), D::Error> {
+        T::deserialize_in_place(self, place, &mut ())
+    }
+
+    /// Interprets this value as the given type, storing it in `place`. This may reuse resources
+    /// held by the existing value.
+    pub fn get_into_using<T: Deserialize<D, Ctx>, Ctx: ?Sized>(
+        self,
+        place: &mut T,
+        context: &mut Ctx,
+    ) -> Result<(), D::Error> {
+        T::deserialize_in_place(self, place, context)
+    }
+
+    /// Interprets this value as the given type, borrowing data from the input of the
+    /// deserializer.
+    pub fn get_borrowed<'de, T: BorrowDeserialize<'de, D>>(self) -> Result<T, D::Error>
+    where
+        D: BorrowDeserializer<'de>,
+    {
+        T::deserialize_borrowed(self, &mut ())
+    }
+
+    /// Interprets this value as the given type, borrowing data from the input of the
+    /// deserializer.
+    pub fn get_borrowed_using<'de, T: BorrowDeserialize<'de, D, Ctx>, Ctx: ?Sized>(
+        self,
+        context: &mut Ctx,
+    ) -> Result<T, D::Error>
+    where
+        D: BorrowDeserializer<'de>,
+    {
+        T::deserialize_borrowed(self, context)
+    }
+
     /// Checks whether this value is `null`, a format-dependent literal representing either a
     /// default, or the absence of a "real" value. If this returns `true`, the value is consumed
     /// and should not be used again.
@@ -229,6 +516,39 @@ impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
         )
     }
 
+    /// Gets the [`ValueKind`] of this value without reading it, if it can be determined ahead of
+    /// time. See [`Deserializer::peek_kind`].
+    pub fn peek_kind(&mut self) -> Result<ValueKind, D::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        self.source.peek_kind()
+    }
+
+    /// Attempts to read this value using the given function. If the attempt fails and the
+    /// deserializer supports checkpoints (see [`Deserializer::checkpoint`]), the deserializer is
+    /// rewound and this returns `Ok(None)`, leaving the value to be read again in a different way.
+    /// Otherwise, the error from the attempt is returned.
+    pub fn speculate<R>(
+        &mut self,
+        f: impl FnOnce(Value<D>) -> Result<R, D::Error>,
+    ) -> Result<Option<R>, D::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        let has_checkpoint = self.source.checkpoint()?;
+        match f(Value::new(self.source, self.done_flag)) {
+            Ok(res) => {
+                if has_checkpoint {
+                    self.source.release();
+                }
+                Ok(Some(res))
+            }
+            Err(_) if has_checkpoint => {
+                self.source.rewind();
+                *self.done_flag = false;
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     /// Interprets this value as a [`bool`].
     pub fn get_bool(self) -> Result<bool, D::Error> {
         assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
@@ -269,6 +589,14 @@ impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
         Ok(res)
     }
 
+    /// Interprets this value as an [`i128`].
+    pub fn get_i128(self) -> Result<i128, D::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        let res = self.source.get_i128()?;
+        *self.done_flag = true;
+        Ok(res)
+    }
+
     /// Interprets this value as a [`u8`].
     pub fn get_u8(self) -> Result<u8, D::Error> {
         assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
@@ -301,6 +629,14 @@ impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
         Ok(res)
     }
 
+    /// Interprets this value as a [`u128`].
+    pub fn get_u128(self) -> Result<u128, D::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        let res = self.source.get_u128()?;
+        *self.done_flag = true;
+        Ok(res)
+    }
+
     /// Interprets this value as a [`f32`].
     pub fn get_f32(self) -> Result<f32, D::Error> {
         assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
@@ -333,6 +669,26 @@ impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
         Ok(res)
     }
 
+    /// Interprets this value as a string, borrowing it from the input of the deserializer if
+    /// possible.
+    pub fn get_borrowed_str<'de>(self) -> Result<Cow<'de, str>, D::Error>
+    where
+        D: BorrowDeserializer<'de>,
+    {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        let res = self.source.read_borrowed_str()?;
+        *self.done_flag = true;
+        Ok(res)
+    }
+
+    /// Interprets this value as a byte string.
+    pub fn get_bytes(self) -> Result<Cow<'a, [u8]>, D::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        let res = self.source.read_bytes()?;
+        *self.done_flag = true;
+        Ok(res)
+    }
+
     /// Interprets this value as an enum tag. The names of the possible tags (or a subset of them)
     /// are provided by a given [`NameMap`]. Depending on the underlying serialization format, this
     /// may accept a string, an integer index, or both.
@@ -353,11 +709,27 @@ impl<'a, D: Deserializer + ?Sized> Value<'a, D> {
         Ok(List::new(self.source, self.done_flag, len))
     }
 
+    /// Asserts that this value is a map.
+    pub fn into_map(self) -> Result<Map<'a, D>, D::Error> {
+        assert!(!*self.done_flag, "{}", INVALID_STATE_ERROR);
+        let len = self.source.open_map()?;
+        Ok(Map::new(self.source, self.done_flag, len))
+    }
+
+    /// Interprets this value as a string and parses it using [`FromStr`](core::str::FromStr).
+    /// Parse errors are reported as custom errors, as with [`Value::validate_with`].
+    pub fn get_parsed<T: core::str::FromStr>(self) -> Result<T, D::Error>
+    where
+        T::Err: core::error::Error + Send + Sync + 'static,
+    {
+        self.validate_with(|value| Ok(value.get_str()?.parse()))
+    }
+
     /// Uses the given closure to deserialize from this [`Value`], allowing a custom error to
     /// be returned and encoded as a `D::Error`. This is typically used when validation is
     /// performed on the deserialized data. If deserializer errors contain position information,
     /// the custom error will be tagged with this value's location.
-    pub fn validate_with<R, E: std::error::Error + Send + Sync + 'static>(
+    pub fn validate_with<R, E: core::error::Error + Send + Sync + 'static>(
         self,
         f: impl FnOnce(Value<D>) -> Result<Result<R, E>, D::Error>,
     ) -> Result<R, D::Error> {
@@ -411,7 +783,7 @@ impl<'a, O: Outliner + ?Sized> Struct<'a, O> {
 
     /// Gets the value of a named field in the struct. Note that fields must be accessed in the
     /// order they are defined.
-    pub fn field(&mut self, name: &'static str) -> Result<Value<O>, O::Error> {
+    pub fn field(&mut self, name: &'static str) -> Result<Value<'_, O>, O::Error> {
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
         self.source.push_field(name)?;
@@ -440,6 +812,44 @@ impl<'a, D: Deserializer + ?Sized> Struct<'a, D> {
     ) -> Result<T, D::Error> {
         T::deserialize_content(self, context)
     }
+
+    /// Gets the value for inlined/flattened struct within this struct, storing it in `place`.
+    pub fn inline_get_into_using<T: DeserializeStruct<D, Ctx>, Ctx: ?Sized>(
+        &mut self,
+        place: &mut T,
+        context: &mut Ctx,
+    ) -> Result<(), D::Error> {
+        T::deserialize_content_in_place(self, place, context)
+    }
+
+    /// Reads part of the struct (typically a single field) using the given function. If it fails
+    /// and the underlying [`Deserializer`] is collecting errors (see [`crate::ErrorCollector`]),
+    /// the error is recorded, the remainder of the part is skipped, and this returns `None`. The
+    /// struct can then continue to be read, but should ultimately fail with
+    /// [`Struct::error_recovered`].
+    pub fn recover<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, D::Error>,
+    ) -> Result<Option<R>, D::Error> {
+        assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
+        let Some(depth) = self.source.recovery_depth() else {
+            return f(self).map(Some);
+        };
+        match f(self) {
+            Ok(res) => Ok(Some(res)),
+            Err(err) => {
+                self.source.recover(depth, err)?;
+                self.ready_flag = true;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Constructs the error returned by a struct which could not be read because of errors that
+    /// were recovered from using [`Struct::recover`].
+    pub fn error_recovered(&self) -> D::Error {
+        self.source.error(Box::new(RecoveredError))
+    }
 }
 
 impl<'a, S: Serializer + ?Sized> Struct<'a, S> {
@@ -494,7 +904,7 @@ impl<'a, O: Outliner + ?Sized> Tuple<'a, O> {
     }
 
     /// Gets the [`Value`] for the next element in the tuple, asserting that one exists.
-    pub fn element(&mut self) -> Result<Value<O>, O::Error> {
+    pub fn element(&mut self) -> Result<Value<'_, O>, O::Error> {
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
         self.source.push_element()?;
@@ -510,6 +920,40 @@ impl<'a, O: Outliner + ?Sized> Tuple<'a, O> {
     }
 }
 
+impl<'a, D: Deserializer + ?Sized> Tuple<'a, D> {
+    /// Gets the values for a sequence of elements within this tuple, as described by a
+    /// [`DeserializeTuple`]. For example, this can be used to read several heterogeneous
+    /// elements into a Rust tuple in one call.
+    pub fn inline_get<T: DeserializeTuple<D>>(&mut self) -> Result<T, D::Error> {
+        T::deserialize_content(self, &mut ())
+    }
+
+    /// Gets the values for a sequence of elements within this tuple, as described by a
+    /// [`DeserializeTuple`].
+    pub fn inline_get_using<T: DeserializeTuple<D, Ctx>, Ctx: ?Sized>(
+        &mut self,
+        context: &mut Ctx,
+    ) -> Result<T, D::Error> {
+        T::deserialize_content(self, context)
+    }
+}
+
+impl<'a, S: Serializer + ?Sized> Tuple<'a, S> {
+    /// Writes a sequence of elements within this tuple, as described by a [`SerializeTuple`].
+    pub fn inline_put<T: SerializeTuple<S>>(&mut self, value: &T) -> Result<(), S::Error> {
+        T::serialize_content(value, self, &mut ())
+    }
+
+    /// Writes a sequence of elements within this tuple, as described by a [`SerializeTuple`].
+    pub fn inline_put_using<T: SerializeTuple<S, Ctx>, Ctx: ?Sized>(
+        &mut self,
+        value: &T,
+        context: &mut Ctx,
+    ) -> Result<(), S::Error> {
+        T::serialize_content(value, self, context)
+    }
+}
+
 /// The error message for a panic that occurs due to an attempt to push an item to a list when
 /// its remaining length is zero.
 pub const LIST_OVERFLOW_ERROR: &str = "list has/expects no more items";
@@ -555,7 +999,7 @@ impl<'a, O: Outliner + ?Sized> List<'a, O> {
     }
 
     /// Asserts that there is another item in the list, returning its value.
-    pub fn push(&mut self) -> Result<Value<O>, O::Error> {
+    pub fn push(&mut self) -> Result<Value<'_, O>, O::Error> {
         assert!(!self.is_len_known || self.rem_len > 0, "{}", LIST_OVERFLOW_ERROR);
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
@@ -578,7 +1022,7 @@ impl<'a, D: Deserializer + ?Sized> List<'a, D> {
     /// Attempts to get the next value from the list, returning `Ok(None)` if the end of the list
     /// has been reached.
     #[allow(clippy::should_implement_trait)] // Requires lending iterator
-    pub fn next(&mut self) -> Result<Option<Value<D>>, D::Error> {
+    pub fn next(&mut self) -> Result<Option<Value<'_, D>>, D::Error> {
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
         Ok(if self.source.next_item()? {
@@ -589,3 +1033,97 @@ impl<'a, D: Deserializer + ?Sized> List<'a, D> {
         })
     }
 }
+
+/// The error message for a panic that occurs due to an attempt to add an entry to a map when
+/// its remaining length is zero.
+pub const MAP_OVERFLOW_ERROR: &str = "map has/expects no more entries";
+
+/// The error message for a panic that occurs due to an attempt to close a map before all entries
+/// have been read or written.
+pub const MAP_UNDERFLOW_ERROR: &str = "map has/expects more entries and may not be closed yet";
+
+/// A wrapper over an [`Outliner`] which has an opened map at the top of its stack.
+/// 
+/// This is a "helper" wrapper intended to provide a convenient interface and enforce correct usage
+/// of the API.
+#[must_use]
+pub struct Map<'a, O: Outliner + ?Sized> {
+    source: &'a mut O,
+    done_flag: &'a mut bool,
+    ready_flag: bool,
+    at_value: bool,
+    is_len_known: bool,
+    rem_len: usize,
+}
+
+impl<'a, O: Outliner + ?Sized> Map<'a, O> {
+    /// Constructs a new [`Map`] wrapper over the given [`Outliner`], asserting that it has an
+    /// opened map at the top of its stack. `done_flag` will be set to true when the map is
+    /// popped.
+    pub fn new(source: &'a mut O, done_flag: &'a mut bool, rem_len: Option<usize>) -> Self {
+        Self {
+            source,
+            done_flag,
+            ready_flag: true,
+            at_value: false,
+            is_len_known: rem_len.is_some(),
+            rem_len: rem_len.unwrap_or(0),
+        }
+    }
+
+    /// Gets the number of entries remaining in the map, if known.
+    pub fn rem_len(&self) -> Option<usize> {
+        if self.is_len_known {
+            Some(self.rem_len)
+        } else {
+            None
+        }
+    }
+
+    /// Asserts that there is another entry in the map, returning the value of its key. The value
+    /// of the entry must be accessed, using [`Map::value`], before the next key.
+    pub fn key(&mut self) -> Result<Value<'_, O>, O::Error> {
+        assert!(!self.is_len_known || self.rem_len > 0, "{}", MAP_OVERFLOW_ERROR);
+        assert!(self.ready_flag && !self.at_value, "{}", INVALID_STATE_ERROR);
+        self.ready_flag = false;
+        self.at_value = true;
+        self.rem_len = self.rem_len.wrapping_sub(1);
+        self.source.push_key()?;
+        Ok(Value::new(self.source, &mut self.ready_flag))
+    }
+
+    /// Gets the value of the entry whose key was most recently accessed.
+    pub fn value(&mut self) -> Result<Value<'_, O>, O::Error> {
+        assert!(self.ready_flag && self.at_value, "{}", INVALID_STATE_ERROR);
+        self.ready_flag = false;
+        self.at_value = false;
+        self.source.push_value()?;
+        Ok(Value::new(self.source, &mut self.ready_flag))
+    }
+
+    /// Asserts that there are no more entries in the map and closes it.
+    pub fn close(self) -> Result<(), O::Error> {
+        assert!(self.ready_flag && !self.at_value, "{}", INVALID_STATE_ERROR);
+        assert!(!self.is_len_known || self.rem_len == 0, "{}", MAP_UNDERFLOW_ERROR);
+        self.source.close_map()?;
+        *self.done_flag = true;
+        Ok(())
+    }
+}
+
+impl<'a, D: Deserializer + ?Sized> Map<'a, D> {
+    /// Attempts to get the key of the next entry in the map, returning `Ok(None)` if the end of
+    /// the map has been reached. The value of the entry must be accessed, using [`Map::value`],
+    /// before the next key.
+    pub fn next_key(&mut self) -> Result<Option<Value<'_, D>>, D::Error> {
+        assert!(self.ready_flag && !self.at_value, "{}", INVALID_STATE_ERROR);
+        self.ready_flag = false;
+        Ok(if self.source.next_key()? {
+            self.at_value = true;
+            Some(Value::new(self.source, &mut self.ready_flag))
+        } else {
+            *self.done_flag = true;
+            None
+        })
+    }
+}
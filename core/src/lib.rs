@@ -1,16 +1,39 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+mod bytes;
+mod char_range;
+mod check;
+mod collect;
 pub mod deserialize;
+mod erased;
 mod helper;
 mod name_map;
+#[cfg(feature = "std")]
+mod os_str;
 mod outliner;
+mod path;
+mod record;
 pub mod serialize;
 mod text_reader;
 mod text_writer;
+mod transcode;
+mod tree;
 
 pub use serdere_derive::{Deserialize, Serialize};
-pub use deserialize::{Deserialize, Deserializer};
+pub use bytes::Bytes;
+pub use char_range::CharRange;
+pub use check::*;
+pub use collect::*;
+pub use deserialize::{Deserialize, Deserializer, ValueKind};
+pub use erased::*;
 pub use helper::*;
 pub use name_map::{FixedNameMap, NameMap};
 pub use outliner::*;
-pub use serialize::{Serialize, Serializer};
+pub use path::*;
+pub use record::*;
+pub use serialize::{Serialize, SerializeIter, Serializer};
 pub use text_reader::*;
 pub use text_writer::*;
+pub use transcode::*;
+pub use tree::*;
@@ -1,4 +1,4 @@
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
 /// An immutable lookup table which associates `&static str`'s to values of type `T`.
 #[derive(Debug)]
@@ -7,7 +7,7 @@ pub struct NameMap<T>([(&'static str, T)]);
 
 impl<T> NameMap<T> {
     /// Begins a lookup into this [`NameMap`].
-    pub fn lookup(&self) -> NameMapLookup<T> {
+    pub fn lookup(&self) -> NameMapLookup<'_, T> {
         NameMapLookup {
             cands: &self.0,
             input_len: 0,
@@ -47,7 +47,7 @@ impl<T: Copy, const N: usize> FixedNameMap<T, N> {
     pub const fn unfix(&self) -> &NameMap<T> {
         let inner: &[_] = &self.0;
         // SAFETY: Transmuting to `repr(transparent)` wrapper.
-        unsafe { std::mem::transmute(inner) }
+        unsafe { core::mem::transmute(inner) }
     }
 }
 
@@ -0,0 +1,141 @@
+use crate::deserialize::{Deserialize, Deserializer, InvalidOsStrError};
+use crate::serialize::{Serialize, Serializer};
+use crate::Value;
+use alloc::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// The character which marks a string as using the fallback encoding for an [`OsStr`]. This is
+/// a noncharacter, so it shouldn't appear at the start of any legitimate string, but such strings
+/// are still encoded using the fallback to keep the representation unambiguous.
+const FALLBACK_MARKER: char = '\u{FFFF}';
+
+/// A platform-specific code unit of an [`OsStr`].
+#[cfg(windows)]
+type Unit = u16;
+
+/// A platform-specific code unit of an [`OsStr`].
+#[cfg(not(windows))]
+type Unit = u8;
+
+/// The number of hexadecimal digits used to encode a [`Unit`].
+const UNIT_DIGITS: usize = core::mem::size_of::<Unit>() * 2;
+
+/// Gets the platform-specific code units for the given [`OsStr`].
+fn to_units(value: &OsStr) -> Vec<Unit> {
+    #[cfg(unix)]
+    return std::os::unix::ffi::OsStrExt::as_bytes(value).to_vec();
+    #[cfg(windows)]
+    return std::os::windows::ffi::OsStrExt::encode_wide(value).collect();
+    #[cfg(not(any(unix, windows)))]
+    return value.as_encoded_bytes().to_vec();
+}
+
+/// Constructs an [`OsString`] from its platform-specific code units, returning [`None`] if they
+/// do not form a valid string on this platform.
+fn from_units(units: Vec<Unit>) -> Option<OsString> {
+    #[cfg(unix)]
+    return Some(std::os::unix::ffi::OsStringExt::from_vec(units));
+    #[cfg(windows)]
+    return Some(std::os::windows::ffi::OsStringExt::from_wide(&units));
+    #[cfg(not(any(unix, windows)))]
+    return String::from_utf8(units).ok().map(OsString::from);
+}
+
+/// Encodes an [`OsStr`] as a string. Strings which are valid Unicode are encoded as-is. Other
+/// strings are encoded as [`FALLBACK_MARKER`] followed by the hexadecimal representation of
+/// their platform-specific code units.
+fn encode(value: &OsStr) -> Cow<'_, str> {
+    match value.to_str() {
+        Some(str) if !str.starts_with(FALLBACK_MARKER) => Cow::Borrowed(str),
+        _ => {
+            let mut res = String::new();
+            res.push(FALLBACK_MARKER);
+            for unit in to_units(value) {
+                for i in (0..UNIT_DIGITS).rev() {
+                    let digit = (unit >> (4 * i)) & 0xF;
+                    res.push(char::from_digit(digit.into(), 16).unwrap());
+                }
+            }
+            Cow::Owned(res)
+        }
+    }
+}
+
+/// Decodes an [`OsString`] from the representation produced by [`encode`], returning [`None`]
+/// if it is not valid.
+fn decode(str: Cow<str>) -> Option<OsString> {
+    let Some(digits) = str.strip_prefix(FALLBACK_MARKER) else {
+        return Some(OsString::from(str.into_owned()));
+    };
+    if digits.len() % UNIT_DIGITS != 0 {
+        return None;
+    }
+    let mut units = Vec::with_capacity(digits.len() / UNIT_DIGITS);
+    for chunk in digits.as_bytes().chunks(UNIT_DIGITS) {
+        let mut unit: Unit = 0;
+        for &digit in chunk {
+            unit = unit << 4 | char::from(digit).to_digit(16)? as Unit;
+        }
+        units.push(unit);
+    }
+    from_units(units)
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for OsStr {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_str(&encode(self))
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for OsString {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_str(&encode(self))
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Path {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_str(&encode(self.as_os_str()))
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for PathBuf {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_str(&encode(self.as_os_str()))
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for OsString {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+        value.validate_with(|value| Ok(decode(value.get_str()?).ok_or(InvalidOsStrError)))
+    }
+}
+
+impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for PathBuf {
+    const NULLABLE: bool = false;
+    fn deserialize(value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        OsString::deserialize(value, context).map(PathBuf::from)
+    }
+}
+
+#[test]
+fn test_fallback() {
+    assert_eq!(encode(OsStr::new("abc")), "abc");
+    let marked = OsString::from("\u{FFFF}abc");
+    assert_eq!(decode(encode(&marked)), Some(marked));
+    assert_eq!(decode(Cow::Borrowed("\u{FFFF}6g")), None);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let invalid = OsStr::from_bytes(b"a\xFFb");
+        assert_eq!(encode(invalid), "\u{FFFF}61ff62");
+        assert_eq!(decode(encode(invalid)).as_deref(), Some(invalid));
+    }
+}
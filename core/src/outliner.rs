@@ -9,11 +9,18 @@ pub trait Outliner {
     /// limited to errors in the downstream data sink. For deserialization, this may be due to
     /// malformed input, or an error in the upstream data source. Incorrect API usage should
     /// generally panic, rather than return an error.
-    type Error: std::error::Error;
+    type Error: core::error::Error;
 
     /// Indicates whether the underlying serialization format supports `null` literals.
     fn supports_null(&self) -> bool;
 
+    /// Indicates whether the underlying serialization format is human-readable. This allows
+    /// [`crate::Serialize`] and [`crate::Deserialize`] implementations to choose between
+    /// representations, e.g. writing a timestamp as a formatted string for a human-readable
+    /// format and as an integer for a compact one. The serializer and deserializer for a format
+    /// must agree on this.
+    fn is_human_readable(&self) -> bool;
+
     /// Assuming that the top item on the stack is a value, asserts that it is a `null` literal
     /// and pops it. `null` is a format-dependent literal representing either a default, or the
     /// absence of a "real" value. This method may only be called if [`Outliner::supports_null`]
@@ -28,10 +35,32 @@ pub trait Outliner {
     /// string has been reached, popping it from the stack.
     fn close_str(&mut self) -> Result<(), Self::Error>;
 
+    /// Assuming that the top item on the stack is a value, asserts that it is a byte string,
+    /// popping it and pushing an opened byte string onto the stack.
+    fn open_bytes(&mut self) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is an opened byte string, asserts that the end of
+    /// the byte string has been reached, popping it from the stack.
+    fn close_bytes(&mut self) -> Result<(), Self::Error>;
+
     /// Assuming that the top item on the stack is a value, asserts that it is an ordered
     /// collection of named fields, popping it and pushing an opened struct onto the stack.
     fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error>;
 
+    /// Like [`Outliner::open_struct`], but also provides the names of all of the fields of the
+    /// struct, in the order they will be pushed. This allows formats which need to know the
+    /// fields of a struct up front (e.g. to write a fixed-length header) to do so. This should
+    /// only be used when every field is always present. The default implementation ignores the
+    /// field names.
+    fn open_struct_with_fields(
+        &mut self,
+        type_name: Option<&'static str>,
+        fields: &'static [&'static str],
+    ) -> Result<(), Self::Error> {
+        let _ = fields;
+        self.open_struct(type_name)
+    }
+
     /// Assuming that the top item on the stack is an opened struct, asserts that the next field
     /// exists and has the given name, pushing the value of the field onto the stack. Regardless
     /// of name, fields must be always considered in the struct-defined order.
@@ -60,4 +89,16 @@ pub trait Outliner {
     /// Assuming that the top item on the stack is an opened list, asserts that it has no
     /// remaining items and pops it from the stack.
     fn close_list(&mut self) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is an opened map, asserts that it has at least
+    /// one more entry, pushing the key of the entry onto the stack as a value.
+    fn push_key(&mut self) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is an opened map whose most recent key has been
+    /// popped, pushes the value of the corresponding entry onto the stack.
+    fn push_value(&mut self) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is an opened map, asserts that it has no
+    /// remaining entries and pops it from the stack.
+    fn close_map(&mut self) -> Result<(), Self::Error>;
 }
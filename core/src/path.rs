@@ -0,0 +1,635 @@
+use crate::deserialize::{ValueKind, NO_CHECKPOINT_ERROR};
+use crate::{Deserializer, NameMap, Number, Outliner, ShapeDeserializer};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// A [`Deserializer`] which wraps another [`Deserializer`], tracking the logical path to the value
+/// that is currently being read. The path is built from the struct fields, tuple elements, list
+/// items and map entries that are descended into (e.g. by the [`crate::Struct`],
+/// [`crate::Tuple`], [`crate::List`] and [`crate::Map`] helpers), and is attached to every
+/// error as a [`PathError`].
+///
+/// Map entries are identified by their key when it is read as a string, number, or name.
+/// Otherwise, they are identified by their index.
+pub struct PathTracker<D> {
+    inner: D,
+    state: PathState,
+}
+
+/// The state of the path, as tracked by a [`PathTracker`].
+#[derive(Default)]
+struct PathState {
+    frames: Vec<Frame>,
+
+    /// For each checkpoint taken using [`Deserializer::checkpoint`], the number of frames at the
+    /// time it was taken, along with the key of the top frame. Reading the value doesn't modify
+    /// anything else in the frames below it.
+    checkpoints: Vec<(usize, Option<Option<String>>)>,
+}
+
+/// An opened collection on the stack of a [`PathTracker`].
+#[derive(Default)]
+struct Frame {
+    /// The segment of the path which identifies the child that is currently being read, or
+    /// [`None`] if no child is being read.
+    segment: Option<PathSegment>,
+
+    /// The number of children that have been pushed onto the stack so far.
+    len: usize,
+
+    /// While the key of a map entry is being read, this is `Some`, and contains the text of the
+    /// key, if it has been read so far.
+    key: Option<Option<String>>,
+}
+
+impl<D> PathTracker<D> {
+    /// Wraps the given [`Deserializer`], assuming that its stack consists of a single value.
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            state: PathState::default(),
+        }
+    }
+
+    /// Gets the path to the value that is currently being read.
+    pub fn path(&self) -> Path {
+        self.state.path()
+    }
+
+    /// Gets the underlying [`Deserializer`].
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Unwraps this [`PathTracker`], returning the underlying [`Deserializer`].
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl PathState {
+    /// Gets the path to the value that is currently being read.
+    fn path(&self) -> Path {
+        Path(self.frames.iter().filter_map(|f| f.segment.clone()).collect())
+    }
+
+    /// Attaches the current path to the error of the given result, if any.
+    fn wrap<T, E>(&self, res: Result<T, E>) -> Result<T, PathError<E>> {
+        res.map_err(|inner| self.error(inner))
+    }
+
+    /// Attaches the current path to the given error.
+    fn error<E>(&self, inner: E) -> PathError<E> {
+        PathError {
+            path: self.path(),
+            inner,
+        }
+    }
+
+    /// Gets the top frame on the stack, asserting that there is one.
+    fn top(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("top of the stack is not an opened collection")
+    }
+
+    /// Sets the segment for the next child of the top frame to its index, and returns it.
+    fn next_index(&mut self) -> &mut Frame {
+        let top = self.top();
+        top.segment = Some(PathSegment::Index(top.len));
+        top.len += 1;
+        top
+    }
+
+    /// Restores the stack after the underlying [`Deserializer`] has been unwound to the given
+    /// depth, using [`Deserializer::unwind`].
+    fn unwind(&mut self, depth: usize) {
+        self.frames.truncate(depth);
+        if let Some(top) = self.frames.last_mut() {
+            top.segment = None;
+            top.key = None;
+        }
+    }
+
+    /// Indicates whether the key of a map entry is being read.
+    fn at_key(&self) -> bool {
+        self.frames.last().is_some_and(|f| f.key.is_some())
+    }
+
+    /// Records the given text as part of the key of a map entry, if one is being read.
+    fn capture(&mut self, text: &str) {
+        if let Some(Frame { key: Some(key), .. }) = self.frames.last_mut() {
+            key.get_or_insert_with(String::new).push_str(text);
+        }
+    }
+
+    /// Records the given name, as looked up in a [`NameMap`], as the key of a map entry, if one
+    /// is being read.
+    fn capture_name(&mut self, names: &'static NameMap<usize>, index: usize) {
+        if let Some((name, _)) = names.entries().find(|(_, i)| **i == index) {
+            self.capture(name)
+        }
+    }
+}
+
+impl<D: Outliner> Outliner for PathTracker<D> {
+    type Error = PathError<D::Error>;
+
+    fn supports_null(&self) -> bool {
+        self.inner.supports_null()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.pop_null();
+        self.state.wrap(res)
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.open_str();
+        self.state.wrap(res)
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.close_str();
+        self.state.wrap(res)
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.open_bytes();
+        self.state.wrap(res)
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.close_bytes();
+        self.state.wrap(res)
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let res = self.inner.open_struct(type_name);
+        self.state.wrap(res)?;
+        self.state.frames.push(Frame::default());
+        Ok(())
+    }
+
+    fn open_struct_with_fields(
+        &mut self,
+        type_name: Option<&'static str>,
+        fields: &'static [&'static str],
+    ) -> Result<(), Self::Error> {
+        let res = self.inner.open_struct_with_fields(type_name, fields);
+        self.state.wrap(res)?;
+        self.state.frames.push(Frame::default());
+        Ok(())
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.state.top().segment = Some(PathSegment::Field(name));
+        let res = self.inner.push_field(name);
+        self.state.wrap(res)
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.close_struct();
+        self.state.wrap(res)?;
+        self.state.frames.pop();
+        Ok(())
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let res = self.inner.open_tuple(type_name);
+        self.state.wrap(res)?;
+        self.state.frames.push(Frame::default());
+        Ok(())
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        self.state.next_index();
+        let res = self.inner.push_element();
+        self.state.wrap(res)
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.close_tuple();
+        self.state.wrap(res)?;
+        self.state.frames.pop();
+        Ok(())
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        self.state.next_index();
+        let res = self.inner.push_item();
+        self.state.wrap(res)
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.close_list();
+        self.state.wrap(res)?;
+        self.state.frames.pop();
+        Ok(())
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.push_key();
+        self.state.wrap(res)?;
+        self.state.top().key = Some(None);
+        Ok(())
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        let top = self.state.top();
+        let key = top.key.take().expect("top of the stack is not an opened map");
+        top.segment = Some(match key {
+            Some(key) => PathSegment::Key(key),
+            None => PathSegment::Index(top.len),
+        });
+        top.len += 1;
+        let res = self.inner.push_value();
+        self.state.wrap(res)
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.close_map();
+        self.state.wrap(res)?;
+        self.state.frames.pop();
+        Ok(())
+    }
+}
+
+/// Implements a method of [`Deserializer`] for [`PathTracker`] which pops a value from the
+/// stack. If the value is the key of a map entry, it is recorded as part of the path.
+macro_rules! path_get {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self) -> Result<$ty, Self::Error> {
+            let res = self.inner.$name();
+            let res = self.state.wrap(res)?;
+            if self.state.at_key() {
+                self.state.capture(&res.to_string());
+            }
+            Ok(res)
+        }
+    };
+}
+
+impl<D: Deserializer> Deserializer for PathTracker<D> {
+    path_get!(get_bool, bool);
+    path_get!(get_i8, i8);
+    path_get!(get_i16, i16);
+    path_get!(get_i32, i32);
+    path_get!(get_i64, i64);
+    path_get!(get_i128, i128);
+    path_get!(get_u8, u8);
+    path_get!(get_u16, u16);
+    path_get!(get_u32, u32);
+    path_get!(get_u64, u64);
+    path_get!(get_u128, u128);
+    path_get!(get_f32, f32);
+    path_get!(get_f64, f64);
+    path_get!(get_char, char);
+
+    fn next_char(&mut self) -> Result<Option<char>, Self::Error> {
+        let res = self.inner.next_char();
+        let res = self.state.wrap(res)?;
+        if let Some(ch) = res {
+            self.state.capture(ch.encode_utf8(&mut [0; 4]));
+        }
+        Ok(res)
+    }
+
+    fn flush_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
+        let res = self.inner.flush_str();
+        let res = self.state.wrap(res)?;
+        self.state.capture(&res);
+        Ok(res)
+    }
+
+    fn skip_str(&mut self) -> Result<(), Self::Error> {
+        let res = self.inner.skip_str();
+        self.state.wrap(res)
+    }
+
+    fn read_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
+        let res = self.inner.read_str();
+        let res = self.state.wrap(res)?;
+        self.state.capture(&res);
+        Ok(res)
+    }
+
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        let res = self.inner.next_bytes_chunk();
+        self.state.wrap(res)
+    }
+
+    fn flush_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        let res = self.inner.flush_bytes();
+        self.state.wrap(res)
+    }
+
+    fn read_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        let res = self.inner.read_bytes();
+        self.state.wrap(res)
+    }
+
+    fn flush_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        let res = self.inner.flush_name(names);
+        let res = self.state.wrap(res)?;
+        self.state.capture_name(names, res);
+        Ok(res)
+    }
+
+    fn get_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        let res = self.inner.get_name(names);
+        let res = self.state.wrap(res)?;
+        self.state.capture_name(names, res);
+        Ok(res)
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        let res = self.inner.get_tag(max_index, names);
+        let res = self.state.wrap(res)?;
+        self.state.capture_name(names, res);
+        Ok(res)
+    }
+
+    fn check_null(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.check_null();
+        self.state.wrap(res)
+    }
+
+    fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = self.inner.open_list();
+        let res = self.state.wrap(res)?;
+        self.state.frames.push(Frame::default());
+        Ok(res)
+    }
+
+    fn next_item(&mut self) -> Result<bool, Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.next_item();
+        if self.state.wrap(res)? {
+            self.state.next_index();
+            Ok(true)
+        } else {
+            self.state.frames.pop();
+            Ok(false)
+        }
+    }
+
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = self.inner.open_map();
+        let res = self.state.wrap(res)?;
+        self.state.frames.push(Frame::default());
+        Ok(res)
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        self.state.top().segment = None;
+        let res = self.inner.next_key();
+        if self.state.wrap(res)? {
+            self.state.top().key = Some(None);
+            Ok(true)
+        } else {
+            self.state.frames.pop();
+            Ok(false)
+        }
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        let res = self.inner.peek_kind();
+        self.state.wrap(res)
+    }
+
+    fn recovery_depth(&self) -> Option<usize> {
+        self.inner.recovery_depth()
+    }
+
+    fn recover(&mut self, depth: usize, err: Self::Error) -> Result<(), Self::Error> {
+        let res = self.inner.recover(depth, err.inner);
+        self.state.wrap(res)?;
+        self.state.unwind(depth);
+        Ok(())
+    }
+
+    fn unwind(&mut self, depth: usize) -> bool {
+        if self.inner.unwind(depth) {
+            self.state.unwind(depth);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        let res = self.state.wrap(self.inner.checkpoint())?;
+        if res {
+            let len = self.state.frames.len();
+            let key = self.state.frames.last().and_then(|f| f.key.clone());
+            self.state.checkpoints.push((len, key));
+        }
+        Ok(res)
+    }
+
+    fn rewind(&mut self) {
+        let (len, key) = self.state.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.rewind();
+        self.state.frames.truncate(len);
+        if let Some(top) = self.state.frames.last_mut() {
+            top.key = key;
+        }
+    }
+
+    fn release(&mut self) {
+        self.state.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.release();
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        self.state.error(self.inner.error(source))
+    }
+
+    fn error_invalid_name(&self, names: &'static NameMap<usize>) -> Self::Error {
+        self.state.error(self.inner.error_invalid_name(names))
+    }
+
+    fn error_invalid_index(&self, index: usize) -> Self::Error {
+        self.state.error(self.inner.error_invalid_index(index))
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        self.state.error(self.inner.error_missing_item())
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        // The error is tagged to the list, rather than its most recent item
+        let mut path = self.state.path();
+        if self.state.frames.last().is_some_and(|f| f.segment.is_some()) {
+            path.0.pop();
+        }
+        PathError {
+            path,
+            inner: self.inner.error_extra_item(),
+        }
+    }
+}
+
+impl<D: ShapeDeserializer> ShapeDeserializer for PathTracker<D> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        let res = self.inner.get_number();
+        let res = self.state.wrap(res)?;
+        if self.state.at_key() {
+            match res {
+                Number::Unsigned(x) => self.state.capture(&x.to_string()),
+                Number::Signed(x) => self.state.capture(&x.to_string()),
+                Number::Float(x) => self.state.capture(&x.to_string()),
+            }
+        }
+        Ok(res)
+    }
+
+    fn has_str_keys(&self) -> bool {
+        self.inner.has_str_keys()
+    }
+}
+
+/// The logical path to a value within a data structure, e.g. `config.servers[2].port`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Path(Vec<PathSegment>);
+
+/// A component of a [`Path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of a struct.
+    Field(&'static str),
+
+    /// An element of a tuple, an item of a list, or an entry of a map whose key couldn't be
+    /// recorded.
+    Index(usize),
+
+    /// An entry of a map, identified by the text of its key.
+    Key(String),
+}
+
+impl Path {
+    /// Gets the segments of this path, from outermost to innermost.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    /// Indicates whether this is the path to the root value.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 => f.write_str(name)?,
+                PathSegment::Field(name) => write!(f, ".{}", name)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::Key(key) if is_identifier(key) && i == 0 => f.write_str(key)?,
+                PathSegment::Key(key) if is_identifier(key) => write!(f, ".{}", key)?,
+                PathSegment::Key(key) => write!(f, "[{:?}]", key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Determines whether the given map key can be displayed in a [`Path`] in the same way as a
+/// field name.
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|ch| ch.is_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+/// An error produced by a [`PathTracker`], which wraps an error from the underlying
+/// [`Deserializer`] with the [`Path`] to the value being read when it occurred.
+#[derive(Debug)]
+pub struct PathError<E> {
+    path: Path,
+    inner: E,
+}
+
+impl<E> PathError<E> {
+    /// Gets the path to the value being read when this error occurred.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the underlying error.
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Unwraps this error, returning the underlying error.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: Display> Display for PathError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            self.inner.fmt(f)
+        } else {
+            write!(f, "{}: {}", self.path, self.inner)
+        }
+    }
+}
+
+impl<E: core::error::Error> core::error::Error for PathError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.inner.source()
+    }
+}
+
+#[test]
+fn test_path() {
+    use crate::{Tree, TreeDeserializer, Value};
+    use alloc::collections::BTreeMap;
+    let tree = Tree::Struct {
+        type_name: None,
+        fields: vec![(
+            "servers",
+            Tree::List(vec![Tree::Map(vec![
+                (Tree::Str("main".to_string()), Tree::I32(80)),
+                (Tree::Str("backup".to_string()), Tree::I32(-1)),
+            ])]),
+        )],
+    };
+    let mut d = PathTracker::new(TreeDeserializer::new(&tree));
+    let err = Value::with(&mut d, |value| {
+        let mut st = value.into_struct(None, None)?;
+        st.field("servers")?
+            .get::<Vec<BTreeMap<String, u16>>>()?;
+        st.close()
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.path().segments(),
+        [
+            PathSegment::Field("servers"),
+            PathSegment::Index(0),
+            PathSegment::Key("backup".to_string())
+        ]
+    );
+    assert_eq!(err.to_string(), "servers[0].backup: value is out of range");
+}
@@ -0,0 +1,1229 @@
+use crate::{Deserializer, NameMap, Number, Outliner, Serialize, Serializer};
+use crate::{ShapeDeserializer, TreeError, TreeSerializer, Value, ValueKind};
+use crate::deserialize::NO_CHECKPOINT_ERROR;
+use crate::INVALID_STATE_ERROR;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// An [`Outliner`] which wraps another [`Outliner`], recording every call made on it into a
+/// log. This is mainly useful for testing and debugging [`Serialize`] and
+/// [`crate::Deserialize`] implementations. Only calls which succeed are recorded.
+pub struct Recorder<O> {
+    inner: O,
+    log: Log,
+}
+
+/// A log of the calls made on a [`Recorder`].
+#[derive(Default)]
+struct Log {
+    records: Vec<Record>,
+    depth: usize,
+
+    /// For each checkpoint taken using [`Deserializer::checkpoint`], the number of records and
+    /// the depth at the time it was taken. Calls made while reading a value speculatively are
+    /// removed from the log if the deserializer is rewound.
+    checkpoints: Vec<(usize, usize)>,
+}
+
+/// A call made on an [`Outliner`], as recorded by a [`Recorder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// The number of opened items on the stack at the time of the call, not including the item
+    /// opened or closed by the call.
+    pub depth: usize,
+
+    /// The method that was called, along with its arguments and result.
+    pub event: Event,
+}
+
+/// Identifies a method call on an [`Outliner`], along with its arguments and result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    PopNull,
+    OpenStr,
+    CloseStr,
+    OpenBytes,
+    CloseBytes,
+    OpenStruct(Option<&'static str>),
+    OpenStructWithFields(Option<&'static str>, &'static [&'static str]),
+    PushField(&'static str),
+    CloseStruct,
+    OpenTuple(Option<&'static str>),
+    PushElement,
+    CloseTuple,
+    PushItem,
+    CloseList,
+    PushKey,
+    PushValue,
+    CloseMap,
+    PutBool(bool),
+    PutI8(i8),
+    PutI16(i16),
+    PutI32(i32),
+    PutI64(i64),
+    PutI128(i128),
+    PutU8(u8),
+    PutU16(u16),
+    PutU32(u32),
+    PutU64(u64),
+    PutU128(u128),
+    PutF32(f32),
+    PutF64(f64),
+    PutChar(char),
+    AppendChar(char),
+    AppendStr(String),
+    PutStr(String),
+    AppendBytes(Vec<u8>),
+    PutBytes(Vec<u8>),
+    PutTag {
+        max_index: usize,
+        index: usize,
+        name: Option<&'static str>,
+    },
+    OpenListSized(usize),
+    OpenMapSized(usize, bool),
+    OpenListUnsized,
+    OpenMapUnsized(bool),
+    GetBool(bool),
+    GetI8(i8),
+    GetI16(i16),
+    GetI32(i32),
+    GetI64(i64),
+    GetI128(i128),
+    GetU8(u8),
+    GetU16(u16),
+    GetU32(u32),
+    GetU64(u64),
+    GetU128(u128),
+    GetF32(f32),
+    GetF64(f64),
+    GetChar(char),
+    NextChar(Option<char>),
+    FlushStr(String),
+    SkipStr,
+    ReadStr(String),
+    NextBytesChunk(Option<Vec<u8>>),
+    FlushBytes(Vec<u8>),
+    ReadBytes(Vec<u8>),
+    FlushName(usize),
+    GetName(usize),
+    GetTag { max_index: usize, index: usize },
+    CheckNull(bool),
+    OpenList(Option<usize>),
+    NextItem(bool),
+    OpenMap(Option<usize>),
+    NextKey(bool),
+    GetNumber(Number),
+}
+
+impl<O> Recorder<O> {
+    /// Constructs a new [`Recorder`] wrapping the given [`Outliner`], with an empty log.
+    pub fn new(inner: O) -> Self {
+        Self {
+            inner,
+            log: Log::default(),
+        }
+    }
+
+    /// Gets the calls that have been recorded so far.
+    pub fn log(&self) -> &[Record] {
+        &self.log.records
+    }
+
+    /// Gets the underlying [`Outliner`].
+    pub fn inner(&self) -> &O {
+        &self.inner
+    }
+
+    /// Gets the underlying [`Outliner`] and the calls that were recorded on it.
+    pub fn into_parts(self) -> (O, Vec<Record>) {
+        (self.inner, self.log.records)
+    }
+}
+
+impl Log {
+    /// Records a call which does not change the number of opened items on the stack.
+    fn record(&mut self, event: Event) {
+        self.records.push(Record {
+            depth: self.depth,
+            event,
+        });
+    }
+
+    /// Records a call which opens an item on the stack.
+    fn open(&mut self, event: Event) {
+        self.record(event);
+        self.depth += 1;
+    }
+
+    /// Records a call which closes an item on the stack.
+    fn close(&mut self, event: Event) {
+        self.depth -= 1;
+        self.record(event);
+    }
+}
+
+/// Records the calls made when serializing a value of type `T`.
+pub fn record<T: Serialize<Recorder<TreeSerializer>> + ?Sized>(
+    value: &T,
+) -> Result<Vec<Record>, TreeError> {
+    record_using(value, &mut ())
+}
+
+/// Records the calls made when serializing a value of type `T`.
+pub fn record_using<T: Serialize<Recorder<TreeSerializer>, Ctx> + ?Sized, Ctx: ?Sized>(
+    value: &T,
+    context: &mut Ctx,
+) -> Result<Vec<Record>, TreeError> {
+    let mut s = Recorder::new(TreeSerializer::new());
+    Value::with(&mut s, |v| value.serialize(v, context))?;
+    Ok(s.into_parts().1)
+}
+
+impl<O: Outliner> Outliner for Recorder<O> {
+    type Error = O::Error;
+
+    fn supports_null(&self) -> bool {
+        self.inner.supports_null()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.inner.pop_null()?;
+        self.log.record(Event::PopNull);
+        Ok(())
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_str()?;
+        self.log.open(Event::OpenStr);
+        Ok(())
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_str()?;
+        self.log.close(Event::CloseStr);
+        Ok(())
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_bytes()?;
+        self.log.open(Event::OpenBytes);
+        Ok(())
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_bytes()?;
+        self.log.close(Event::CloseBytes);
+        Ok(())
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        self.inner.open_struct(type_name)?;
+        self.log.open(Event::OpenStruct(type_name));
+        Ok(())
+    }
+
+    fn open_struct_with_fields(
+        &mut self,
+        type_name: Option<&'static str>,
+        fields: &'static [&'static str],
+    ) -> Result<(), Self::Error> {
+        self.inner.open_struct_with_fields(type_name, fields)?;
+        self.log.open(Event::OpenStructWithFields(type_name, fields));
+        Ok(())
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.inner.push_field(name)?;
+        self.log.record(Event::PushField(name));
+        Ok(())
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_struct()?;
+        self.log.close(Event::CloseStruct);
+        Ok(())
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        self.inner.open_tuple(type_name)?;
+        self.log.open(Event::OpenTuple(type_name));
+        Ok(())
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_element()?;
+        self.log.record(Event::PushElement);
+        Ok(())
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_tuple()?;
+        self.log.close(Event::CloseTuple);
+        Ok(())
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_item()?;
+        self.log.record(Event::PushItem);
+        Ok(())
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_list()?;
+        self.log.close(Event::CloseList);
+        Ok(())
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_key()?;
+        self.log.record(Event::PushKey);
+        Ok(())
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        self.inner.push_value()?;
+        self.log.record(Event::PushValue);
+        Ok(())
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        self.inner.close_map()?;
+        self.log.close(Event::CloseMap);
+        Ok(())
+    }
+}
+
+/// Implements a method of [`Serializer`] for [`Recorder`] which records a single event
+/// containing the value that was written.
+macro_rules! record_put {
+    ($name:ident, $ty:ty, $event:ident) => {
+        fn $name(&mut self, value: $ty) -> Result<(), Self::Error> {
+            self.inner.$name(value)?;
+            self.log.record(Event::$event(value));
+            Ok(())
+        }
+    };
+}
+
+impl<S: Serializer> Serializer for Recorder<S> {
+    record_put!(put_bool, bool, PutBool);
+    record_put!(put_i8, i8, PutI8);
+    record_put!(put_i16, i16, PutI16);
+    record_put!(put_i32, i32, PutI32);
+    record_put!(put_i64, i64, PutI64);
+    record_put!(put_i128, i128, PutI128);
+    record_put!(put_u8, u8, PutU8);
+    record_put!(put_u16, u16, PutU16);
+    record_put!(put_u32, u32, PutU32);
+    record_put!(put_u64, u64, PutU64);
+    record_put!(put_u128, u128, PutU128);
+    record_put!(put_f32, f32, PutF32);
+    record_put!(put_f64, f64, PutF64);
+    record_put!(put_char, char, PutChar);
+    record_put!(append_char, char, AppendChar);
+
+    fn append_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.inner.append_str(value)?;
+        self.log.record(Event::AppendStr(value.to_owned()));
+        Ok(())
+    }
+
+    fn put_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.inner.put_str(value)?;
+        self.log.record(Event::PutStr(value.to_owned()));
+        Ok(())
+    }
+
+    fn append_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.inner.append_bytes(value)?;
+        self.log.record(Event::AppendBytes(value.to_vec()));
+        Ok(())
+    }
+
+    fn put_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.inner.put_bytes(value)?;
+        self.log.record(Event::PutBytes(value.to_vec()));
+        Ok(())
+    }
+
+    fn put_tag(
+        &mut self,
+        max_index: usize,
+        index: usize,
+        name: Option<&'static str>,
+    ) -> Result<(), Self::Error> {
+        self.inner.put_tag(max_index, index, name)?;
+        self.log.record(Event::PutTag {
+            max_index,
+            index,
+            name,
+        });
+        Ok(())
+    }
+
+    fn open_list_sized(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.inner.open_list_sized(len)?;
+        self.log.open(Event::OpenListSized(len));
+        Ok(())
+    }
+
+    fn open_map_sized(&mut self, len: usize, str_keys: bool) -> Result<(), Self::Error> {
+        self.inner.open_map_sized(len, str_keys)?;
+        self.log.open(Event::OpenMapSized(len, str_keys));
+        Ok(())
+    }
+
+    fn supports_unsized(&self) -> bool {
+        self.inner.supports_unsized()
+    }
+
+    fn open_list_unsized(&mut self) -> Result<(), Self::Error> {
+        self.inner.open_list_unsized()?;
+        self.log.open(Event::OpenListUnsized);
+        Ok(())
+    }
+
+    fn open_map_unsized(&mut self, str_keys: bool) -> Result<(), Self::Error> {
+        self.inner.open_map_unsized(str_keys)?;
+        self.log.open(Event::OpenMapUnsized(str_keys));
+        Ok(())
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        self.inner.error(source)
+    }
+}
+
+/// Implements a method of [`Deserializer`] for [`Recorder`] which records a single event
+/// containing the value that was read.
+macro_rules! record_get {
+    ($name:ident, $ty:ty, $event:ident) => {
+        fn $name(&mut self) -> Result<$ty, Self::Error> {
+            let res = self.inner.$name()?;
+            self.log.record(Event::$event(res));
+            Ok(res)
+        }
+    };
+}
+
+impl<D: Deserializer> Deserializer for Recorder<D> {
+    record_get!(get_bool, bool, GetBool);
+    record_get!(get_i8, i8, GetI8);
+    record_get!(get_i16, i16, GetI16);
+    record_get!(get_i32, i32, GetI32);
+    record_get!(get_i64, i64, GetI64);
+    record_get!(get_i128, i128, GetI128);
+    record_get!(get_u8, u8, GetU8);
+    record_get!(get_u16, u16, GetU16);
+    record_get!(get_u32, u32, GetU32);
+    record_get!(get_u64, u64, GetU64);
+    record_get!(get_u128, u128, GetU128);
+    record_get!(get_f32, f32, GetF32);
+    record_get!(get_f64, f64, GetF64);
+    record_get!(get_char, char, GetChar);
+    record_get!(check_null, bool, CheckNull);
+
+    fn next_char(&mut self) -> Result<Option<char>, Self::Error> {
+        let res = self.inner.next_char()?;
+        if res.is_some() {
+            self.log.record(Event::NextChar(res));
+        } else {
+            self.log.close(Event::NextChar(res));
+        }
+        Ok(res)
+    }
+
+    fn flush_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
+        let res = self.inner.flush_str()?;
+        self.log.close(Event::FlushStr(res.to_string()));
+        Ok(res)
+    }
+
+    fn skip_str(&mut self) -> Result<(), Self::Error> {
+        self.inner.skip_str()?;
+        self.log.close(Event::SkipStr);
+        Ok(())
+    }
+
+    fn read_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
+        let res = self.inner.read_str()?;
+        self.log.record(Event::ReadStr(res.to_string()));
+        Ok(res)
+    }
+
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        let res = self.inner.next_bytes_chunk()?;
+        if let Some(chunk) = res {
+            self.log.record(Event::NextBytesChunk(Some(chunk.to_vec())));
+        } else {
+            self.log.close(Event::NextBytesChunk(None));
+        }
+        Ok(res)
+    }
+
+    fn flush_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        let res = self.inner.flush_bytes()?;
+        self.log.close(Event::FlushBytes(res.to_vec()));
+        Ok(res)
+    }
+
+    fn read_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        let res = self.inner.read_bytes()?;
+        self.log.record(Event::ReadBytes(res.to_vec()));
+        Ok(res)
+    }
+
+    fn flush_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        let res = self.inner.flush_name(names)?;
+        self.log.close(Event::FlushName(res));
+        Ok(res)
+    }
+
+    fn get_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        let res = self.inner.get_name(names)?;
+        self.log.record(Event::GetName(res));
+        Ok(res)
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        let index = self.inner.get_tag(max_index, names)?;
+        self.log.record(Event::GetTag { max_index, index });
+        Ok(index)
+    }
+
+    fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = self.inner.open_list()?;
+        self.log.open(Event::OpenList(res));
+        Ok(res)
+    }
+
+    fn next_item(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.next_item()?;
+        if res {
+            self.log.record(Event::NextItem(res));
+        } else {
+            self.log.close(Event::NextItem(res));
+        }
+        Ok(res)
+    }
+
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        let res = self.inner.open_map()?;
+        self.log.open(Event::OpenMap(res));
+        Ok(res)
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.next_key()?;
+        if res {
+            self.log.record(Event::NextKey(res));
+        } else {
+            self.log.close(Event::NextKey(res));
+        }
+        Ok(res)
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        self.inner.peek_kind()
+    }
+
+    fn recovery_depth(&self) -> Option<usize> {
+        self.inner.recovery_depth()
+    }
+
+    fn recover(&mut self, depth: usize, err: Self::Error) -> Result<(), Self::Error> {
+        self.inner.recover(depth, err)?;
+        self.log.depth = depth;
+        Ok(())
+    }
+
+    fn unwind(&mut self, depth: usize) -> bool {
+        if self.inner.unwind(depth) {
+            self.log.depth = depth;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        let res = self.inner.checkpoint()?;
+        if res {
+            let checkpoint = (self.log.records.len(), self.log.depth);
+            self.log.checkpoints.push(checkpoint);
+        }
+        Ok(res)
+    }
+
+    fn rewind(&mut self) {
+        let (len, depth) = self.log.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.rewind();
+        self.log.records.truncate(len);
+        self.log.depth = depth;
+    }
+
+    fn release(&mut self) {
+        self.log.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.inner.release();
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        self.inner.error(source)
+    }
+
+    fn error_invalid_name(&self, names: &'static NameMap<usize>) -> Self::Error {
+        self.inner.error_invalid_name(names)
+    }
+
+    fn error_invalid_index(&self, index: usize) -> Self::Error {
+        self.inner.error_invalid_index(index)
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        self.inner.error_missing_item()
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        self.inner.error_extra_item()
+    }
+}
+
+impl<D: ShapeDeserializer> ShapeDeserializer for Recorder<D> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        let res = self.inner.get_number()?;
+        self.log.record(Event::GetNumber(res));
+        Ok(res)
+    }
+
+    fn has_str_keys(&self) -> bool {
+        self.inner.has_str_keys()
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for _ in 0..self.depth {
+            f.write_str("    ")?;
+        }
+        self.event.fmt(f)
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Event::PopNull => write!(f, "pop_null()"),
+            Event::OpenStr => write!(f, "open_str()"),
+            Event::CloseStr => write!(f, "close_str()"),
+            Event::OpenBytes => write!(f, "open_bytes()"),
+            Event::CloseBytes => write!(f, "close_bytes()"),
+            Event::OpenStruct(type_name) => write!(f, "open_struct({:?})", type_name),
+            Event::OpenStructWithFields(type_name, fields) => {
+                write!(f, "open_struct_with_fields({:?}, {:?})", type_name, fields)
+            }
+            Event::PushField(name) => write!(f, "push_field({:?})", name),
+            Event::CloseStruct => write!(f, "close_struct()"),
+            Event::OpenTuple(type_name) => write!(f, "open_tuple({:?})", type_name),
+            Event::PushElement => write!(f, "push_element()"),
+            Event::CloseTuple => write!(f, "close_tuple()"),
+            Event::PushItem => write!(f, "push_item()"),
+            Event::CloseList => write!(f, "close_list()"),
+            Event::PushKey => write!(f, "push_key()"),
+            Event::PushValue => write!(f, "push_value()"),
+            Event::CloseMap => write!(f, "close_map()"),
+            Event::PutBool(x) => write!(f, "put_bool({:?})", x),
+            Event::PutI8(x) => write!(f, "put_i8({:?})", x),
+            Event::PutI16(x) => write!(f, "put_i16({:?})", x),
+            Event::PutI32(x) => write!(f, "put_i32({:?})", x),
+            Event::PutI64(x) => write!(f, "put_i64({:?})", x),
+            Event::PutI128(x) => write!(f, "put_i128({:?})", x),
+            Event::PutU8(x) => write!(f, "put_u8({:?})", x),
+            Event::PutU16(x) => write!(f, "put_u16({:?})", x),
+            Event::PutU32(x) => write!(f, "put_u32({:?})", x),
+            Event::PutU64(x) => write!(f, "put_u64({:?})", x),
+            Event::PutU128(x) => write!(f, "put_u128({:?})", x),
+            Event::PutF32(x) => write!(f, "put_f32({:?})", x),
+            Event::PutF64(x) => write!(f, "put_f64({:?})", x),
+            Event::PutChar(x) => write!(f, "put_char({:?})", x),
+            Event::AppendChar(x) => write!(f, "append_char({:?})", x),
+            Event::AppendStr(x) => write!(f, "append_str({:?})", x),
+            Event::PutStr(x) => write!(f, "put_str({:?})", x),
+            Event::AppendBytes(x) => write!(f, "append_bytes({:?})", x),
+            Event::PutBytes(x) => write!(f, "put_bytes({:?})", x),
+            Event::PutTag {
+                max_index,
+                index,
+                name,
+            } => write!(f, "put_tag({:?}, {:?}, {:?})", max_index, index, name),
+            Event::OpenListSized(len) => write!(f, "open_list_sized({:?})", len),
+            Event::OpenMapSized(len, str_keys) => {
+                write!(f, "open_map_sized({:?}, {:?})", len, str_keys)
+            }
+            Event::OpenListUnsized => write!(f, "open_list_unsized()"),
+            Event::OpenMapUnsized(str_keys) => write!(f, "open_map_unsized({:?})", str_keys),
+            Event::GetBool(x) => write!(f, "get_bool() -> {:?}", x),
+            Event::GetI8(x) => write!(f, "get_i8() -> {:?}", x),
+            Event::GetI16(x) => write!(f, "get_i16() -> {:?}", x),
+            Event::GetI32(x) => write!(f, "get_i32() -> {:?}", x),
+            Event::GetI64(x) => write!(f, "get_i64() -> {:?}", x),
+            Event::GetI128(x) => write!(f, "get_i128() -> {:?}", x),
+            Event::GetU8(x) => write!(f, "get_u8() -> {:?}", x),
+            Event::GetU16(x) => write!(f, "get_u16() -> {:?}", x),
+            Event::GetU32(x) => write!(f, "get_u32() -> {:?}", x),
+            Event::GetU64(x) => write!(f, "get_u64() -> {:?}", x),
+            Event::GetU128(x) => write!(f, "get_u128() -> {:?}", x),
+            Event::GetF32(x) => write!(f, "get_f32() -> {:?}", x),
+            Event::GetF64(x) => write!(f, "get_f64() -> {:?}", x),
+            Event::GetChar(x) => write!(f, "get_char() -> {:?}", x),
+            Event::NextChar(x) => write!(f, "next_char() -> {:?}", x),
+            Event::FlushStr(x) => write!(f, "flush_str() -> {:?}", x),
+            Event::SkipStr => write!(f, "skip_str()"),
+            Event::ReadStr(x) => write!(f, "read_str() -> {:?}", x),
+            Event::NextBytesChunk(x) => write!(f, "next_bytes_chunk() -> {:?}", x),
+            Event::FlushBytes(x) => write!(f, "flush_bytes() -> {:?}", x),
+            Event::ReadBytes(x) => write!(f, "read_bytes() -> {:?}", x),
+            Event::FlushName(x) => write!(f, "flush_name(..) -> {:?}", x),
+            Event::GetName(x) => write!(f, "get_name(..) -> {:?}", x),
+            Event::GetTag { max_index, index } => {
+                write!(f, "get_tag({:?}, ..) -> {:?}", max_index, index)
+            }
+            Event::CheckNull(x) => write!(f, "check_null() -> {:?}", x),
+            Event::OpenList(x) => write!(f, "open_list() -> {:?}", x),
+            Event::NextItem(x) => write!(f, "next_item() -> {:?}", x),
+            Event::OpenMap(x) => write!(f, "open_map() -> {:?}", x),
+            Event::NextKey(x) => write!(f, "next_key() -> {:?}", x),
+            Event::GetNumber(x) => write!(f, "get_number() -> {:?}", x),
+        }
+    }
+}
+
+/// A [`Deserializer`] which reads from a log of calls recorded by a [`Recorder`]. The log may be
+/// recorded from either a [`Serializer`] or a [`Deserializer`]: each call made on the
+/// [`Replayer`] consumes the corresponding recorded call, and returns the value that was written
+/// or read by it.
+pub struct Replayer<'a> {
+    log: &'a [Record],
+    pos: usize,
+    str: Option<(String, usize)>,
+    bytes: Option<(Vec<u8>, bool)>,
+    checkpoints: Vec<usize>,
+}
+
+/// An error that can occur when deserializing using a [`Replayer`].
+#[derive(thiserror::Error, Debug)]
+pub enum ReplayError {
+    #[error("{0}")]
+    Custom(Box<dyn core::error::Error + Send + Sync>),
+    #[error("expected call compatible with {expected}, found {found}")]
+    Mismatch {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("fewer items than expected")]
+    MissingItems,
+    #[error("more items than expected")]
+    ExcessItems,
+}
+
+impl<'a> Replayer<'a> {
+    /// Constructs a new [`Replayer`] which reads from the given log. The stack initially
+    /// consists of a single value item.
+    pub fn new(log: &'a [Record]) -> Self {
+        Self {
+            log,
+            pos: 0,
+            str: None,
+            bytes: None,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Asserts that the entire log has been consumed.
+    pub fn close(self) -> Result<(), ReplayError> {
+        match self.log.get(self.pos) {
+            Some(record) => Err(ReplayError::Mismatch {
+                expected: "end of log",
+                found: record.event.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Consumes the next event in the log if `f` accepts it, returning the result of `f`.
+    /// Otherwise, returns an error which says the event doesn't match the given method.
+    fn take<R>(
+        &mut self,
+        expected: &'static str,
+        f: impl FnOnce(&'a Event) -> Option<R>,
+    ) -> Result<R, ReplayError> {
+        let event = self.log.get(self.pos).map(|record| &record.event);
+        match event.and_then(f) {
+            Some(res) => {
+                self.pos += 1;
+                Ok(res)
+            }
+            None => Err(ReplayError::Mismatch {
+                expected,
+                found: event.map_or("end of log".to_owned(), |event| event.to_string()),
+            }),
+        }
+    }
+
+    /// Consumes the next event in the log if `f` accepts it, returning the result of `f`.
+    fn try_take<R>(&mut self, f: impl FnOnce(&'a Event) -> Option<R>) -> Option<R> {
+        let res = f(&self.log.get(self.pos)?.event)?;
+        self.pos += 1;
+        Some(res)
+    }
+}
+
+impl Outliner for Replayer<'_> {
+    type Error = ReplayError;
+
+    fn supports_null(&self) -> bool {
+        true
+    }
+
+    fn is_human_readable(&self) -> bool {
+        // The log doesn't record which format it came from, so assume the more common case
+        true
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.take("pop_null", |event| match event {
+            Event::PopNull | Event::CheckNull(true) => Some(()),
+            _ => None,
+        })
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        let mut str = self.take("open_str", |event| match event {
+            Event::OpenStr => Some(None),
+            Event::PutStr(str) | Event::ReadStr(str) => Some(Some(str.clone())),
+            _ => None,
+        })?;
+        if str.is_none() {
+            let mut buf = String::new();
+            loop {
+                match self.take("close_str", Some)? {
+                    Event::AppendChar(ch) | Event::NextChar(Some(ch)) => buf.push(*ch),
+                    Event::AppendStr(str) => buf.push_str(str),
+                    Event::FlushStr(str) => {
+                        buf.push_str(str);
+                        break;
+                    }
+                    Event::CloseStr | Event::NextChar(None) | Event::SkipStr => break,
+                    event => {
+                        return Err(ReplayError::Mismatch {
+                            expected: "close_str",
+                            found: event.to_string(),
+                        })
+                    }
+                }
+            }
+            str = Some(buf);
+        }
+        self.str = str.map(|str| (str, 0));
+        Ok(())
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        let (str, pos) = self.str.take().expect(INVALID_STATE_ERROR);
+        if let Some(ch) = str[pos..].chars().next() {
+            return Err(ReplayError::Mismatch {
+                expected: "close_str",
+                found: format!("remaining character {:?}", ch),
+            });
+        }
+        Ok(())
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        let mut bytes = self.take("open_bytes", |event| match event {
+            Event::OpenBytes => Some(None),
+            Event::PutBytes(bytes) | Event::ReadBytes(bytes) => Some(Some(bytes.clone())),
+            _ => None,
+        })?;
+        if bytes.is_none() {
+            let mut buf = Vec::new();
+            loop {
+                match self.take("close_bytes", Some)? {
+                    Event::AppendBytes(chunk) | Event::NextBytesChunk(Some(chunk)) => {
+                        buf.extend_from_slice(chunk)
+                    }
+                    Event::FlushBytes(chunk) => {
+                        buf.extend_from_slice(chunk);
+                        break;
+                    }
+                    Event::CloseBytes | Event::NextBytesChunk(None) => break,
+                    event => {
+                        return Err(ReplayError::Mismatch {
+                            expected: "close_bytes",
+                            found: event.to_string(),
+                        })
+                    }
+                }
+            }
+            bytes = Some(buf);
+        }
+        self.bytes = bytes.map(|bytes| (bytes, false));
+        Ok(())
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        match self.bytes.take() {
+            Some((bytes, false)) if !bytes.is_empty() => Err(ReplayError::Mismatch {
+                expected: "close_bytes",
+                found: "remaining bytes".to_owned(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let _ = type_name;
+        self.take("open_struct", |event| match event {
+            Event::OpenStruct(_) | Event::OpenStructWithFields(..) => Some(()),
+            _ => None,
+        })
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        self.take("push_field", |event| match event {
+            Event::PushField(found) if *found == name => Some(()),
+            _ => None,
+        })
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        self.take("close_struct", |event| match event {
+            Event::CloseStruct => Some(()),
+            _ => None,
+        })
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let _ = type_name;
+        self.take("open_tuple", |event| match event {
+            Event::OpenTuple(_) => Some(()),
+            _ => None,
+        })
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        self.take("push_element", |event| match event {
+            Event::PushElement => Some(()),
+            _ => None,
+        })
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        self.take("close_tuple", |event| match event {
+            Event::CloseTuple => Some(()),
+            _ => None,
+        })
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        if self.next_item()? {
+            Ok(())
+        } else {
+            Err(ReplayError::MissingItems)
+        }
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        if self.next_item()? {
+            Err(ReplayError::ExcessItems)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        if self.next_key()? {
+            Ok(())
+        } else {
+            Err(ReplayError::MissingItems)
+        }
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        self.take("push_value", |event| match event {
+            Event::PushValue => Some(()),
+            _ => None,
+        })
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        if self.next_key()? {
+            Err(ReplayError::ExcessItems)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Implements a method of [`Deserializer`] for [`Replayer`] which consumes a single event
+/// containing the value that was written or read.
+macro_rules! replay_get {
+    ($name:ident, $ty:ty, $put:ident, $get:ident) => {
+        fn $name(&mut self) -> Result<$ty, Self::Error> {
+            self.take(stringify!($name), |event| match event {
+                Event::$put(x) | Event::$get(x) => Some(*x),
+                _ => None,
+            })
+        }
+    };
+}
+
+impl Deserializer for Replayer<'_> {
+    replay_get!(get_bool, bool, PutBool, GetBool);
+    replay_get!(get_i8, i8, PutI8, GetI8);
+    replay_get!(get_i16, i16, PutI16, GetI16);
+    replay_get!(get_i32, i32, PutI32, GetI32);
+    replay_get!(get_i64, i64, PutI64, GetI64);
+    replay_get!(get_i128, i128, PutI128, GetI128);
+    replay_get!(get_u8, u8, PutU8, GetU8);
+    replay_get!(get_u16, u16, PutU16, GetU16);
+    replay_get!(get_u32, u32, PutU32, GetU32);
+    replay_get!(get_u64, u64, PutU64, GetU64);
+    replay_get!(get_u128, u128, PutU128, GetU128);
+    replay_get!(get_f32, f32, PutF32, GetF32);
+    replay_get!(get_f64, f64, PutF64, GetF64);
+    replay_get!(get_char, char, PutChar, GetChar);
+
+    fn next_char(&mut self) -> Result<Option<char>, Self::Error> {
+        let (str, pos) = self.str.as_mut().expect(INVALID_STATE_ERROR);
+        let res = str[*pos..].chars().next();
+        if let Some(ch) = res {
+            *pos += ch.len_utf8();
+        } else {
+            self.str = None;
+        }
+        Ok(res)
+    }
+
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        let (bytes, returned) = self.bytes.as_mut().expect(INVALID_STATE_ERROR);
+        if *returned || bytes.is_empty() {
+            self.bytes = None;
+            return Ok(None);
+        }
+        *returned = true;
+        Ok(self.bytes.as_ref().map(|(bytes, _)| &bytes[..]))
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        let index = self.try_take(|event| match event {
+            Event::PutTag { index, .. } | Event::GetTag { index, .. } => Some(*index),
+            _ => None,
+        });
+        let index = match index {
+            Some(index) => index,
+            None => self.get_name(names)?,
+        };
+        if index <= max_index {
+            Ok(index)
+        } else {
+            Err(self.error_invalid_index(index))
+        }
+    }
+
+    fn get_name(&mut self, names: &'static NameMap<usize>) -> Result<usize, Self::Error> {
+        let index = self.try_take(|event| match event {
+            Event::GetName(index) => Some(*index),
+            _ => None,
+        });
+        match index {
+            Some(index) => Ok(index),
+            None => {
+                self.open_str()?;
+                self.flush_name(names)
+            }
+        }
+    }
+
+    fn check_null(&mut self) -> Result<bool, Self::Error> {
+        Ok(self
+            .try_take(|event| match event {
+                Event::PopNull | Event::CheckNull(true) => Some(true),
+                Event::CheckNull(false) => Some(false),
+                _ => None,
+            })
+            .unwrap_or(false))
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        // A `check_null` call which returned `false` is followed by the call that read the value
+        let mut records = self.log[self.pos..].iter();
+        let mut event = records.next().map(|record| &record.event);
+        if let Some(Event::CheckNull(false)) = event {
+            event = records.next().map(|record| &record.event);
+        }
+        Ok(match event {
+            Some(Event::PopNull | Event::CheckNull(true)) => ValueKind::Null,
+            Some(Event::PutBool(_) | Event::GetBool(_)) => ValueKind::Bool,
+            Some(
+                Event::PutI8(_)
+                | Event::PutI16(_)
+                | Event::PutI32(_)
+                | Event::PutI64(_)
+                | Event::PutI128(_)
+                | Event::PutU8(_)
+                | Event::PutU16(_)
+                | Event::PutU32(_)
+                | Event::PutU64(_)
+                | Event::PutU128(_)
+                | Event::GetI8(_)
+                | Event::GetI16(_)
+                | Event::GetI32(_)
+                | Event::GetI64(_)
+                | Event::GetI128(_)
+                | Event::GetU8(_)
+                | Event::GetU16(_)
+                | Event::GetU32(_)
+                | Event::GetU64(_)
+                | Event::GetU128(_)
+                | Event::GetNumber(Number::Unsigned(_) | Number::Signed(_)),
+            ) => ValueKind::Integer,
+            Some(
+                Event::PutF32(_)
+                | Event::PutF64(_)
+                | Event::GetF32(_)
+                | Event::GetF64(_)
+                | Event::GetNumber(Number::Float(_)),
+            ) => ValueKind::Float,
+            Some(
+                Event::OpenStr
+                | Event::PutStr(_)
+                | Event::ReadStr(_)
+                | Event::PutChar(_)
+                | Event::GetChar(_)
+                | Event::GetName(_),
+            ) => ValueKind::Str,
+            Some(Event::OpenBytes | Event::PutBytes(_) | Event::ReadBytes(_)) => ValueKind::Bytes,
+            Some(
+                Event::OpenStruct(_)
+                | Event::OpenStructWithFields(..)
+                | Event::OpenMap(_)
+                | Event::OpenMapSized(..)
+                | Event::OpenMapUnsized(_),
+            ) => ValueKind::Struct,
+            Some(
+                Event::OpenTuple(_)
+                | Event::OpenList(_)
+                | Event::OpenListSized(_)
+                | Event::OpenListUnsized,
+            ) => ValueKind::List,
+            _ => ValueKind::Unknown,
+        })
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        self.checkpoints.push(self.pos);
+        Ok(true)
+    }
+
+    fn rewind(&mut self) {
+        self.pos = self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.str = None;
+        self.bytes = None;
+    }
+
+    fn release(&mut self) {
+        self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+    }
+
+    fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        self.take("open_list", |event| match event {
+            Event::OpenListSized(len) => Some(Some(*len)),
+            Event::OpenListUnsized => Some(None),
+            Event::OpenList(len) => Some(*len),
+            _ => None,
+        })
+    }
+
+    fn next_item(&mut self) -> Result<bool, Self::Error> {
+        self.take("next_item", |event| match event {
+            Event::PushItem | Event::NextItem(true) => Some(true),
+            Event::CloseList | Event::NextItem(false) => Some(false),
+            _ => None,
+        })
+    }
+
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        self.take("open_map", |event| match event {
+            Event::OpenMapSized(len, _) => Some(Some(*len)),
+            Event::OpenMapUnsized(_) => Some(None),
+            Event::OpenMap(len) => Some(*len),
+            _ => None,
+        })
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        self.take("next_key", |event| match event {
+            Event::PushKey | Event::NextKey(true) => Some(true),
+            Event::CloseMap | Event::NextKey(false) => Some(false),
+            _ => None,
+        })
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        ReplayError::Custom(source)
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        ReplayError::MissingItems
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        ReplayError::ExcessItems
+    }
+}
+
+#[test]
+fn test_record_replay() {
+    let value: (u8, Option<String>, Vec<f32>) = (1, Some("hi".to_owned()), vec![1.0]);
+    let log = record(&value).unwrap();
+    let text: Vec<String> = log.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        text,
+        [
+            "open_tuple(None)",
+            "    push_element()",
+            "    put_u8(1)",
+            "    push_element()",
+            "    put_str(\"hi\")",
+            "    push_element()",
+            "    open_list_sized(1)",
+            "        push_item()",
+            "        put_f32(1.0)",
+            "    close_list()",
+            "close_tuple()",
+        ]
+    );
+    let mut d = Replayer::new(&log);
+    let res: (u8, Option<String>, Vec<f32>) = Value::with(&mut d, |value| value.get()).unwrap();
+    d.close().unwrap();
+    assert_eq!(res, value);
+    let mut d = Replayer::new(&log);
+    assert!(Value::with(&mut d, |value| value.get::<(u8, u8, Vec<f32>)>()).is_err());
+}
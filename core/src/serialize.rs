@@ -1,6 +1,10 @@
 #[allow(unused_imports)]
 use crate::Deserializer;
-use crate::{Outliner, Struct, Value};
+use crate::{Outliner, Struct, Tuple, Value};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 /// An interface for writing arbitrarily-complex data to a data source. This uses a stack-based
 /// API, described in [`Outliner`].
@@ -25,6 +29,10 @@ pub trait Serializer: Outliner {
     /// pops it.
     fn put_i64(&mut self, value: i64) -> Result<(), Self::Error>;
 
+    /// Assuming that the top item on the stack is a value, assigns it to the given [`i128`] and
+    /// pops it.
+    fn put_i128(&mut self, value: i128) -> Result<(), Self::Error>;
+
     /// Assuming that the top item on the stack is a value, assigns it to the given [`u8`] and
     /// pops it.
     fn put_u8(&mut self, value: u8) -> Result<(), Self::Error>;
@@ -41,6 +49,10 @@ pub trait Serializer: Outliner {
     /// pops it.
     fn put_u64(&mut self, value: u64) -> Result<(), Self::Error>;
 
+    /// Assuming that the top item on the stack is a value, assigns it to the given [`u128`] and
+    /// pops it.
+    fn put_u128(&mut self, value: u128) -> Result<(), Self::Error>;
+
     /// Assuming that the top item on the stack is a value, assigns it to the given [`f32`] and
     /// pops it.
     fn put_f32(&mut self, value: f32) -> Result<(), Self::Error>;
@@ -74,6 +86,18 @@ pub trait Serializer: Outliner {
         self.close_str()
     }
 
+    /// Assuming that the top item on the stack is an opened byte string, appends the given bytes
+    /// to it.
+    fn append_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is a value, assigns it to the given byte string
+    /// and pops it.
+    fn put_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.open_bytes()?;
+        self.append_bytes(value)?;
+        self.close_bytes()
+    }
+
     /// Assuming that the top item on the stack is a value, assigns it to an enum "tag". Depending
     /// on the underlying serialization format, this can be written as a string or an integer
     /// index.
@@ -87,6 +111,35 @@ pub trait Serializer: Outliner {
     /// Assuming that the top item on the stack is a value, asserts that it is an ordered list
     /// with the given number of items, popping it and pushing an opened list onto the stack.
     fn open_list_sized(&mut self, len: usize) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is a value, asserts that it is a map with the
+    /// given number of entries, popping it and pushing an opened map onto the stack.
+    ///
+    /// `str_keys` indicates whether every key of the map will be written as a string (see
+    /// [`Serialize::IS_STR`]). Formats may use this to choose a more natural representation for
+    /// the map, e.g. a JSON object rather than an array of pairs.
+    fn open_map_sized(&mut self, len: usize, str_keys: bool) -> Result<(), Self::Error>;
+
+    /// Indicates whether the underlying serialization format can write lists and maps without
+    /// knowing their length up front.
+    fn supports_unsized(&self) -> bool;
+
+    /// Assuming that the top item on the stack is a value, asserts that it is an ordered list
+    /// with an unspecified number of items, popping it and pushing an opened list onto the stack.
+    /// This method may only be called if [`Serializer::supports_unsized`] returns `true`.
+    fn open_list_unsized(&mut self) -> Result<(), Self::Error>;
+
+    /// Assuming that the top item on the stack is a value, asserts that it is a map with an
+    /// unspecified number of entries, popping it and pushing an opened map onto the stack.
+    /// This method may only be called if [`Serializer::supports_unsized`] returns `true`.
+    ///
+    /// `str_keys` has the same meaning as in [`Serializer::open_map_sized`].
+    fn open_map_unsized(&mut self, str_keys: bool) -> Result<(), Self::Error>;
+
+    /// Constructs an error wrapping the given source error. This allows a [`Serialize`]
+    /// implementation to reject values that can't be serialized, e.g. because they violate an
+    /// invariant of their type.
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error;
 }
 
 /// A type which can be serialized using a seserializer of type `S` given access to a context
@@ -98,6 +151,11 @@ pub trait Serialize<S: Serializer + ?Sized, Ctx: ?Sized = ()> {
     /// Wrappers over non-nullable types can use `null` as a "niche" to represent one extra value.
     const NULLABLE: bool;
 
+    /// Indicates whether the `serialize` function always writes a string (i.e. using
+    /// [`Serializer::open_str`], [`Serializer::put_str`] or [`Serializer::put_char`]). Maps whose
+    /// key type is a string are opened with `str_keys` set (see [`Serializer::open_map_sized`]).
+    const IS_STR: bool = false;
+
     /// Writes a value of this type to the given [`Value`].
     fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error>;
 }
@@ -119,12 +177,32 @@ pub fn serialize_struct<
     value: &T,
     context: &mut Ctx,
     type_name: Option<&'static str>,
+    fields: Option<&'static [&'static str]>,
 ) -> Result<(), S::Error> {
-    let mut st = target.into_struct(type_name)?;
+    let mut st = target.into_struct(type_name, fields)?;
     value.serialize_content(&mut st, context)?;
     st.close()
 }
 
+/// A [`Serialize`] which is serialized as a tuple value. This can be used to inline/flatten
+/// the elements of one tuple into another.
+pub trait SerializeTuple<S: Serializer + ?Sized, Ctx: ?Sized = ()>: Serialize<S, Ctx> {
+    /// Writes a value of this type to the given [`Tuple`].
+    fn serialize_content(&self, tuple: &mut Tuple<S>, context: &mut Ctx) -> Result<(), S::Error>;
+}
+
+/// The standard implementation of [`Serialize::serialize`] for a [`SerializeTuple`].
+pub fn serialize_tuple<T: SerializeTuple<S, Ctx> + ?Sized, S: Serializer + ?Sized, Ctx: ?Sized>(
+    target: Value<S>,
+    value: &T,
+    context: &mut Ctx,
+    type_name: Option<&'static str>,
+) -> Result<(), S::Error> {
+    let mut tuple = target.into_tuple(type_name)?;
+    value.serialize_content(&mut tuple, context)?;
+    tuple.close()
+}
+
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for bool {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
@@ -160,6 +238,13 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for i64 {
     }
 }
 
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for i128 {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_i128(*self)
+    }
+}
+
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for u8 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
@@ -188,6 +273,13 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for u64 {
     }
 }
 
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for u128 {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_u128(*self)
+    }
+}
+
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for f32 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
@@ -204,6 +296,7 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for f64 {
 
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for char {
     const NULLABLE: bool = false;
+    const IS_STR: bool = true;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_char(*self)
     }
@@ -211,6 +304,7 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for char {
 
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for str {
     const NULLABLE: bool = false;
+    const IS_STR: bool = true;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_str(self)
     }
@@ -218,6 +312,7 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for str {
 
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for String {
     const NULLABLE: bool = false;
+    const IS_STR: bool = true;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_str(self)
     }
@@ -226,7 +321,7 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for String {
 impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for () {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
-        serialize_struct(value, &(), context, None)
+        serialize_struct(value, &(), context, None, Some(&[]))
     }
 }
 
@@ -250,7 +345,7 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx
             }
         } else {
             // Fallback to using a regular struct
-            let mut st = value.into_struct(Some("Option"))?;
+            let mut st = value.into_struct(Some("Option"), None)?;
             let has_value = st.field("has_value")?;
             if let Some(inner) = self {
                 has_value.put_bool(true)?;
@@ -263,112 +358,582 @@ impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized, T0: Serialize<S, Ctx>, T1: Serialize<S, Ctx>>
-    Serialize<S, Ctx> for (T0, T1)
+macro_rules! impl_tuple {
+    ($($t:ident $i:tt),+) => {
+        impl<S: Serializer + ?Sized, Ctx: ?Sized, $($t: Serialize<S, Ctx>),+> Serialize<S, Ctx>
+            for ($($t,)+)
+        {
+            const NULLABLE: bool = false;
+            fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+                serialize_tuple(value, self, context, None)
+            }
+        }
+
+        impl<S: Serializer + ?Sized, Ctx: ?Sized, $($t: Serialize<S, Ctx>),+>
+            SerializeTuple<S, Ctx> for ($($t,)+)
+        {
+            fn serialize_content(
+                &self,
+                tuple: &mut Tuple<S>,
+                context: &mut Ctx,
+            ) -> Result<(), S::Error> {
+                $(tuple.element()?.put_using(&self.$i, context)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_tuple!(T0 0);
+impl_tuple!(T0 0, T1 1);
+impl_tuple!(T0 0, T1 1, T2 2);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12);
+impl_tuple!(
+    T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13
+);
+impl_tuple!(
+    T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13,
+    T14 14
+);
+impl_tuple!(
+    T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13,
+    T14 14, T15 15
+);
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>, const N: usize> Serialize<S, Ctx>
+    for [T; N]
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_tuple(value, self, context, None)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>, const N: usize>
+    SerializeTuple<S, Ctx> for [T; N]
+{
+    fn serialize_content(&self, tuple: &mut Tuple<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        for item in self.iter() {
+            tuple.element()?.put_using(item, context)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx> for Vec<T> {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_exact_iter_using(self.iter(), context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for [T]
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_exact_iter_using(self.iter(), context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for alloc::collections::VecDeque<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_exact_iter_using(self.iter(), context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for alloc::collections::LinkedList<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_exact_iter_using(self.iter(), context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for alloc::collections::BinaryHeap<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_exact_iter_using(self.iter(), context)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>, H> Serialize<S, Ctx>
+    for std::collections::HashSet<T, H>
 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
-        let mut tuple = value.into_tuple(None)?;
-        tuple.element()?.put_using(&self.0, context)?;
-        tuple.element()?.put_using(&self.1, context)?;
-        tuple.close()
+        value.put_exact_iter_using(self.iter(), context)
     }
 }
 
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for alloc::collections::BTreeSet<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_exact_iter_using(self.iter(), context)
+    }
+}
+
+#[cfg(feature = "std")]
 impl<
         S: Serializer + ?Sized,
         Ctx: ?Sized,
-        T0: Serialize<S, Ctx>,
-        T1: Serialize<S, Ctx>,
-        T2: Serialize<S, Ctx>,
-    > Serialize<S, Ctx> for (T0, T1, T2)
+        K: Serialize<S, Ctx>,
+        V: Serialize<S, Ctx>,
+        H,
+    > Serialize<S, Ctx> for std::collections::HashMap<K, V, H>
 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
-        let mut tuple = value.into_tuple(None)?;
-        tuple.element()?.put_using(&self.0, context)?;
-        tuple.element()?.put_using(&self.1, context)?;
-        tuple.element()?.put_using(&self.2, context)?;
-        tuple.close()
+        value.put_exact_map_iter_using(self.iter(), context)
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>, const N: usize> Serialize<S, Ctx>
-    for [T; N]
+impl<S: Serializer + ?Sized, Ctx: ?Sized, K: Serialize<S, Ctx>, V: Serialize<S, Ctx>>
+    Serialize<S, Ctx> for alloc::collections::BTreeMap<K, V>
 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
-        let mut tuple = value.into_tuple(None)?;
-        for item in self.iter() {
-            tuple.element()?.put_using(item, context)?;
+        value.put_exact_map_iter_using(self.iter(), context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for &T
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        T::serialize(self, value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for Box<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        T::serialize(self, value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for alloc::rc::Rc<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        T::serialize(self, value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for alloc::sync::Arc<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        T::serialize(self, value, context)
+    }
+}
+
+impl<'a, S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ToOwned + ?Sized>
+    Serialize<S, Ctx> for alloc::borrow::Cow<'a, T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        T::serialize(self, value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + Copy> Serialize<S, Ctx>
+    for core::cell::Cell<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        self.get().serialize(value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for core::cell::RefCell<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        match self.try_borrow() {
+            Ok(inner) => inner.serialize(value, context),
+            Err(err) => Err(value.as_raw().error(Box::new(err))),
         }
-        tuple.close()
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx> for Vec<T> {
+#[cfg(feature = "std")]
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for std::sync::Mutex<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        // Poisoning doesn't affect our ability to read the data
+        let guard = self.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        guard.serialize(value, context)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx> + ?Sized> Serialize<S, Ctx>
+    for std::sync::RwLock<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        // Poisoning doesn't affect our ability to read the data
+        let guard = self.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        guard.serialize(value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for core::cmp::Reverse<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        self.0.serialize(value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for core::num::Wrapping<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        self.0.serialize(value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for core::num::Saturating<T>
+{
+    const NULLABLE: bool = T::NULLABLE;
+    const IS_STR: bool = T::IS_STR;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        self.0.serialize(value, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: ?Sized> Serialize<S, Ctx>
+    for core::marker::PhantomData<T>
+{
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
-        let mut list = value.into_list_sized(self.len())?;
-        for item in self.iter() {
-            list.push()?.put_using(item, context)?;
-        }
-        list.close()
+        ().serialize(value, context)
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroI8 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroI8 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_i8((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroI16 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroI16 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_i16((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroI32 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroI32 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_i32((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroI64 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroI64 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_i64((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroU8 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroI128 {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_i128((*self).into())
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroU8 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_u8((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroU16 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroU16 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_u16((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroU32 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroU32 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_u32((*self).into())
     }
 }
 
-impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::num::NonZeroU64 {
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroU64 {
     const NULLABLE: bool = false;
     fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
         value.put_u64((*self).into())
     }
-}
\ No newline at end of file
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::num::NonZeroU128 {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_u128((*self).into())
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::time::Duration {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_struct(value, self, context, Some("Duration"), Some(&["secs", "nanos"]))
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> SerializeStruct<S, Ctx> for core::time::Duration {
+    fn serialize_content(&self, st: &mut Struct<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        st.field("secs")?.put_u64(self.as_secs())?;
+        st.field("nanos")?.put_u32(self.subsec_nanos())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for std::time::SystemTime {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_struct(value, self, context, Some("SystemTime"), Some(&["secs", "nanos"]))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: Serializer + ?Sized, Ctx: ?Sized> SerializeStruct<S, Ctx> for std::time::SystemTime {
+    fn serialize_content(&self, st: &mut Struct<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        // Times are stored as a signed number of seconds relative to `UNIX_EPOCH`, plus a
+        // non-negative number of nanoseconds
+        let (secs, nanos) = match self.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => (i128::from(after.as_secs()), after.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                let secs = -i128::from(before.as_secs());
+                match before.subsec_nanos() {
+                    0 => (secs, 0),
+                    nanos => (secs - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+        let value = st.field("secs")?;
+        let Ok(secs) = i64::try_from(secs) else {
+            let err = crate::deserialize::OutOfRangeError;
+            return Err(value.as_raw().error(Box::new(err)));
+        };
+        value.put_i64(secs)?;
+        st.field("nanos")?.put_u32(nanos)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for core::ops::Range<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_struct(value, self, context, Some("Range"), Some(&["start", "end"]))
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> SerializeStruct<S, Ctx>
+    for core::ops::Range<T>
+{
+    fn serialize_content(&self, st: &mut Struct<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        st.field("start")?.put_using(&self.start, context)?;
+        st.field("end")?.put_using(&self.end, context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for core::ops::RangeInclusive<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_struct(value, self, context, Some("RangeInclusive"), Some(&["start", "end"]))
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> SerializeStruct<S, Ctx>
+    for core::ops::RangeInclusive<T>
+{
+    fn serialize_content(&self, st: &mut Struct<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        st.field("start")?.put_using(self.start(), context)?;
+        st.field("end")?.put_using(self.end(), context)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> Serialize<S, Ctx>
+    for core::ops::Bound<T>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_struct(value, self, context, Some("Bound"), None)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>> SerializeStruct<S, Ctx>
+    for core::ops::Bound<T>
+{
+    fn serialize_content(&self, st: &mut Struct<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        use core::ops::Bound::*;
+        match self {
+            Included(inner) => {
+                st.field("type")?.put_tag(2, 0, Some("Included"))?;
+                st.field("value")?.put_using(inner, context)
+            }
+            Excluded(inner) => {
+                st.field("type")?.put_tag(2, 1, Some("Excluded"))?;
+                st.field("value")?.put_using(inner, context)
+            }
+            Unbounded => st.field("type")?.put_tag(2, 2, Some("Unbounded")),
+        }
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>, E: Serialize<S, Ctx>>
+    Serialize<S, Ctx> for Result<T, E>
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        serialize_struct(value, self, context, Some("Result"), None)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, T: Serialize<S, Ctx>, E: Serialize<S, Ctx>>
+    SerializeStruct<S, Ctx> for Result<T, E>
+{
+    fn serialize_content(&self, st: &mut Struct<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        match self {
+            Ok(inner) => {
+                st.field("type")?.put_tag(1, 0, Some("Ok"))?;
+                st.field("value")?.put_using(inner, context)
+            }
+            Err(inner) => {
+                st.field("type")?.put_tag(1, 1, Some("Err"))?;
+                st.field("value")?.put_using(inner, context)
+            }
+        }
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::cmp::Ordering {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        match self {
+            core::cmp::Ordering::Less => value.put_tag(2, 0, Some("Less")),
+            core::cmp::Ordering::Equal => value.put_tag(2, 1, Some("Equal")),
+            core::cmp::Ordering::Greater => value.put_tag(2, 2, Some("Greater")),
+        }
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::net::IpAddr {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::net::Ipv4Addr {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::net::Ipv6Addr {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::net::SocketAddr {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::net::SocketAddrV4 {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for core::net::SocketAddrV6 {
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+        value.put_display(self)
+    }
+}
+
+/// Wraps an iterator so that it can be serialized as a list, without collecting it first. This
+/// is useful for including a lazily-computed sequence as part of a larger value. The iterator is
+/// cloned each time the [`SerializeIter`] is serialized, so it may be serialized more than once.
+#[derive(Clone)]
+pub struct SerializeIter<I>(I);
+
+impl<I: Iterator + Clone> SerializeIter<I> {
+    /// Wraps the given iterator.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self(iter.into_iter())
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized, I: Iterator + Clone> Serialize<S, Ctx>
+    for SerializeIter<I>
+where
+    I::Item: Serialize<S, Ctx>,
+{
+    const NULLABLE: bool = false;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        value.put_iter_using(self.0.clone(), context)
+    }
+}
@@ -1,5 +1,7 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(feature = "std")]
 use std::io;
-use std::borrow::Cow;
 
 /// An interface for reading characters from a stream.
 pub trait TextReader {
@@ -13,7 +15,7 @@ pub trait TextReader {
     fn peek(&self) -> Option<char>;
 
     /// Identifies a position in the input stream.
-    type Position: Ord + Clone + std::fmt::Debug + std::fmt::Display;
+    type Position: Ord + Clone + core::fmt::Debug + core::fmt::Display;
 
     /// Gets the current position in the stream.
     fn position(&self) -> Self::Position;
@@ -33,7 +35,10 @@ pub trait TextReader {
     /// Returns a string of the characters read up to, but not including, the terminating character.
     /// The stream is advanced past the terminating character. If the end of the input stream is
     /// reached before a terminating character is found, this returns [`None`].
-    fn read_until<R>(&mut self, mut pred: impl FnMut(char) -> Option<R>) -> Option<(Cow<str>, R)> {
+    fn read_until<R>(
+        &mut self,
+        mut pred: impl FnMut(char) -> Option<R>,
+    ) -> Option<(Cow<'_, str>, R)> {
         let mut str = String::new();
         loop {
             let ch = self.next()?;
@@ -63,7 +68,10 @@ impl<'a> TextReader for &'a str {
         StrPosition(self)
     }
 
-    fn read_until<R>(&mut self, mut pred: impl FnMut(char) -> Option<R>) -> Option<(Cow<str>, R)> {
+    fn read_until<R>(
+        &mut self,
+        mut pred: impl FnMut(char) -> Option<R>,
+    ) -> Option<(Cow<'_, str>, R)> {
         let start = *self;
         loop {
             let suffix = *self;
@@ -86,39 +94,47 @@ pub fn prefix<'a>(source: &'a str, suffix: &str) -> &'a str {
 #[derive(Clone, Copy)]
 pub struct StrPosition<'a>(&'a str);
 
+impl<'a> StrPosition<'a> {
+    /// Gets the remainder of the source buffer, starting at this position.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
 impl PartialEq for StrPosition<'_> {
     fn eq(&self, other: &Self) -> bool {
-        std::ptr::eq(self.0, other.0)
+        core::ptr::eq(self.0, other.0)
     }
 }
 
 impl Eq for StrPosition<'_> {}
 
 impl PartialOrd for StrPosition<'_> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for StrPosition<'_> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         (self.0.as_ptr()).cmp(&other.0.as_ptr())
     }
 }
 
-impl std::fmt::Debug for StrPosition<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for StrPosition<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
 
-impl std::fmt::Display for StrPosition<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for StrPosition<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // TODO: Don't display the whole string. Only a portion is needed for context
         write!(f, "around {:?}", self.0)
     }
 }
 
+#[cfg(feature = "std")]
 /// A [`TextReader`] which reads from a [`std::io::Read`] with UTF-8 encoding, tracking position
 /// using [`LineColumnPosition`]. This reader has no internal buffering, so it is recommended to
 /// use a [`std::io::BufReader`] for data that is not already in memory.
@@ -138,6 +154,7 @@ pub struct LineColumnPosition {
     pub column: usize
 }
 
+#[cfg(feature = "std")]
 impl<R: std::io::Read> Utf8Reader<R> {
     /// Constructs a new [`Utf8Reader`] which reads from the given source.
     pub fn new(mut source: R) -> io::Result<Self> {
@@ -150,6 +167,7 @@ impl<R: std::io::Read> Utf8Reader<R> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<R: std::io::Read> TextReader for Utf8Reader<R> {
     fn next(&mut self) -> Option<char> {
         // Advance position
@@ -180,17 +198,18 @@ impl<R: std::io::Read> TextReader for Utf8Reader<R> {
     }
 }
 
-impl std::fmt::Display for LineColumnPosition {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for LineColumnPosition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "at line {}, column {}", self.line + 1, self.column + 1)
     }
 }
 
+#[cfg(feature = "std")]
 /// Reads a single [`char`] from a stream, assuming UTF-8 encoding. Returns [`None`] if the stream
 /// has no data remaining and returns an error if an invalid or partial character is encountered.
 pub fn read_utf8(r: &mut (impl std::io::Read + ?Sized)) -> std::io::Result<Option<char>> {
     let mut x = 0;
-    if r.read(std::slice::from_mut(&mut x))? == 0 {
+    if r.read(core::slice::from_mut(&mut x))? == 0 {
         return Ok(None);
     }
     if x < 0b10000000 {
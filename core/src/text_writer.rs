@@ -1,7 +1,9 @@
+use alloc::string::String;
+
 /// An interface for writing characters to a stream.
 pub trait TextWriter {
     /// The type of error that can occur while writing to the stream.
-    type Error: std::error::Error;
+    type Error: core::error::Error + 'static;
 
     /// Writes a character to the stream.
     fn write_char(&mut self, ch: char) -> Result<(), Self::Error>;
@@ -27,7 +29,7 @@ impl<T: TextWriter + ?Sized> TextWriter for &'_ mut T {
 }
 
 impl TextWriter for String {
-    type Error = std::convert::Infallible;
+    type Error = core::convert::Infallible;
     fn write_char(&mut self, ch: char) -> Result<(), Self::Error> {
         self.push(ch);
         Ok(())
@@ -39,11 +41,13 @@ impl TextWriter for String {
     }
 }
 
+#[cfg(feature = "std")]
 /// A [`TextWriter`] which writes to a [`std::io::Write`] with UTF-8 encoding. This writer has no
 /// internal buffering, so it is recommended to use a [`std::io::BufWriter`] for data that is not
 /// already in memory.
 pub struct Utf8Writer<W: std::io::Write>(W);
 
+#[cfg(feature = "std")]
 impl<W: std::io::Write> Utf8Writer<W> {
     /// Constructs a new [`Utf8Writer`] which writes to the given destination.
     pub fn new(dest: W) -> Self {
@@ -56,6 +60,7 @@ impl<W: std::io::Write> Utf8Writer<W> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<W: std::io::Write> TextWriter for Utf8Writer<W> {
     type Error = std::io::Error;
     fn write_char(&mut self, ch: char) -> Result<(), Self::Error> {
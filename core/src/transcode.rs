@@ -0,0 +1,192 @@
+use crate::deserialize::{Deserializer, ValueKind};
+use crate::serialize::{Serialize, Serializer};
+use crate::{TreeError, TreeSerializer, Value};
+use alloc::boxed::Box;
+
+/// A number read from a [`ShapeDeserializer`], in the most precise representation available.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Number {
+    /// A non-negative integer.
+    Unsigned(u128),
+
+    /// A negative integer.
+    Signed(i128),
+    Float(f64),
+}
+
+/// A [`Deserializer`] which can always identify the kind of a value before reading it, i.e.
+/// [`Deserializer::peek_kind`] never returns [`ValueKind::Unknown`]. This allows values to be
+/// read without a type to guide deserialization, e.g. by [`transcode`].
+pub trait ShapeDeserializer: Deserializer {
+    /// Assuming that the top item on the stack is a value with [`ValueKind::Integer`] or
+    /// [`ValueKind::Float`], pops it and returns it as a [`Number`].
+    fn get_number(&mut self) -> Result<Number, Self::Error>;
+
+    /// Assuming that the top item on the stack is an opened map, indicates whether all of its
+    /// keys are strings. This is passed on as `str_keys` when the map is written by
+    /// [`transcode`] (see [`Serializer::open_map_sized`]).
+    fn has_str_keys(&self) -> bool;
+}
+
+/// The error message for a panic that occurs when [`Deserializer::peek_kind`] returns
+/// [`ValueKind::Unknown`] for a [`ShapeDeserializer`].
+pub const UNKNOWN_KIND_ERROR: &str = "`ShapeDeserializer` returned an unknown value kind";
+
+/// An error that can occur during [`transcode`].
+#[derive(thiserror::Error, Debug)]
+pub enum TranscodeError<DE, SE> {
+    #[error(transparent)]
+    Deserialize(DE),
+    #[error(transparent)]
+    Serialize(SE),
+}
+
+/// Reads a value from a [`ShapeDeserializer`] and writes it to a [`Serializer`], one event at a
+/// time, without building an intermediate representation.
+///
+/// Lists and maps of unknown length can only be streamed if [`Serializer::supports_unsized`]
+/// returns `true`. Otherwise, they will be buffered in a [`crate::Tree`] before being written.
+pub fn transcode<D: ShapeDeserializer + ?Sized, S: Serializer + ?Sized>(
+    value: Value<D>,
+    target: Value<S>,
+) -> Result<(), TranscodeError<D::Error, S::Error>> {
+    let (d, d_done_flag) = value.into_raw();
+    let (s, s_done_flag) = target.into_raw();
+    transcode_raw(d, s)?;
+    *d_done_flag = true;
+    *s_done_flag = true;
+    Ok(())
+}
+
+/// Assuming that the top item on the stack of both `d` and `s` is a value, reads the value from
+/// `d` and writes it to `s`, popping it from both.
+fn transcode_raw<D: ShapeDeserializer + ?Sized, S: Serializer + ?Sized>(
+    d: &mut D,
+    s: &mut S,
+) -> Result<(), TranscodeError<D::Error, S::Error>> {
+    use TranscodeError::Deserialize as De;
+    use TranscodeError::Serialize as Se;
+    match d.peek_kind().map_err(De)? {
+        ValueKind::Null => {
+            d.pop_null().map_err(De)?;
+            if s.supports_null() {
+                s.pop_null().map_err(Se)
+            } else {
+                // Fallback to using the representation of `()`
+                s.open_struct(None).map_err(Se)?;
+                s.close_struct().map_err(Se)
+            }
+        }
+        ValueKind::Bool => s.put_bool(d.get_bool().map_err(De)?).map_err(Se),
+        ValueKind::Integer | ValueKind::Float => match d.get_number().map_err(De)? {
+            Number::Unsigned(x) => match u64::try_from(x) {
+                Ok(x) => s.put_u64(x),
+                Err(_) => s.put_u128(x),
+            },
+            Number::Signed(x) => match i64::try_from(x) {
+                Ok(x) => s.put_i64(x),
+                Err(_) => s.put_i128(x),
+            },
+            Number::Float(x) => s.put_f64(x),
+        }
+        .map_err(Se),
+        ValueKind::Str => {
+            d.open_str().map_err(De)?;
+            s.open_str().map_err(Se)?;
+            while let Some(ch) = d.next_char().map_err(De)? {
+                s.append_char(ch).map_err(Se)?;
+            }
+            s.close_str().map_err(Se)
+        }
+        ValueKind::Bytes => {
+            d.open_bytes().map_err(De)?;
+            s.open_bytes().map_err(Se)?;
+            while let Some(chunk) = d.next_bytes_chunk().map_err(De)? {
+                s.append_bytes(chunk).map_err(Se)?;
+            }
+            s.close_bytes().map_err(Se)
+        }
+        ValueKind::List => match d.open_list().map_err(De)? {
+            Some(len) => {
+                s.open_list_sized(len).map_err(Se)?;
+                transcode_items(d, s)
+            }
+            None if s.supports_unsized() => {
+                s.open_list_unsized().map_err(Se)?;
+                transcode_items(d, s)
+            }
+            None => buffer(d, s, |d, s| {
+                s.open_list_unsized().map_err(Se)?;
+                transcode_items(d, s)
+            }),
+        },
+        ValueKind::Struct => {
+            let len = d.open_map().map_err(De)?;
+            let str_keys = d.has_str_keys();
+            match len {
+                Some(len) => {
+                    s.open_map_sized(len, str_keys).map_err(Se)?;
+                    transcode_entries(d, s)
+                }
+                None if s.supports_unsized() => {
+                    s.open_map_unsized(str_keys).map_err(Se)?;
+                    transcode_entries(d, s)
+                }
+                None => buffer(d, s, |d, s| {
+                    s.open_map_unsized(str_keys).map_err(Se)?;
+                    transcode_entries(d, s)
+                }),
+            }
+        }
+        ValueKind::Unknown => panic!("{}", UNKNOWN_KIND_ERROR),
+    }
+}
+
+/// Assuming that the top item on the stack of both `d` and `s` is an opened list, transcodes
+/// the remaining items of the list and closes it.
+fn transcode_items<D: ShapeDeserializer + ?Sized, S: Serializer + ?Sized>(
+    d: &mut D,
+    s: &mut S,
+) -> Result<(), TranscodeError<D::Error, S::Error>> {
+    while d.next_item().map_err(TranscodeError::Deserialize)? {
+        s.push_item().map_err(TranscodeError::Serialize)?;
+        transcode_raw(d, s)?;
+    }
+    s.close_list().map_err(TranscodeError::Serialize)
+}
+
+/// Assuming that the top item on the stack of both `d` and `s` is an opened map, transcodes
+/// the remaining entries of the map and closes it.
+fn transcode_entries<D: ShapeDeserializer + ?Sized, S: Serializer + ?Sized>(
+    d: &mut D,
+    s: &mut S,
+) -> Result<(), TranscodeError<D::Error, S::Error>> {
+    while d.next_key().map_err(TranscodeError::Deserialize)? {
+        s.push_key().map_err(TranscodeError::Serialize)?;
+        transcode_raw(d, s)?;
+        d.push_value().map_err(TranscodeError::Deserialize)?;
+        s.push_value().map_err(TranscodeError::Serialize)?;
+        transcode_raw(d, s)?;
+    }
+    s.close_map().map_err(TranscodeError::Serialize)
+}
+
+/// Transcodes a value from `d` into a [`crate::Tree`] using the given function, and then writes
+/// the tree to `s`. This is used when `s` needs to know the length of a collection before it can
+/// be written.
+fn buffer<D: ShapeDeserializer + ?Sized, S: Serializer + ?Sized>(
+    d: &mut D,
+    s: &mut S,
+    f: impl FnOnce(
+        &mut D,
+        &mut TreeSerializer,
+    ) -> Result<(), TranscodeError<D::Error, TreeError>>,
+) -> Result<(), TranscodeError<D::Error, S::Error>> {
+    let mut tree_s = TreeSerializer::new();
+    f(d, &mut tree_s).map_err(|err| match err {
+        TranscodeError::Deserialize(err) => TranscodeError::Deserialize(err),
+        TranscodeError::Serialize(err) => TranscodeError::Serialize(s.error(Box::new(err))),
+    })?;
+    let tree = tree_s.finish();
+    Value::with(s, |value| tree.serialize(value, &mut ())).map_err(TranscodeError::Serialize)
+}
@@ -0,0 +1,1336 @@
+use crate::deserialize::{Deserialize, Deserializer, ValueKind, NO_CHECKPOINT_ERROR};
+use crate::serialize::{Serialize, Serializer};
+use crate::transcode::{Number, ShapeDeserializer, UNKNOWN_KIND_ERROR};
+use crate::{NameMap, Outliner, Value, INVALID_STATE_ERROR};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// An owned, format-independent representation of a value, as described by a [`Serializer`].
+/// Any type which implements [`Serialize`] can be converted to a [`Tree`] using [`to_tree`], and
+/// any type which implements [`Deserialize`] can be read back from one using [`from_tree`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tree {
+    /// A `null` literal.
+    Null,
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    Str(String),
+    Bytes(Vec<u8>),
+
+    /// An enum tag, as written by [`Serializer::put_tag`].
+    Tag {
+        max_index: usize,
+        index: usize,
+        name: Option<&'static str>,
+    },
+
+    /// An ordered collection of named fields.
+    Struct {
+        type_name: Option<&'static str>,
+        fields: Vec<(&'static str, Tree)>,
+    },
+
+    /// An ordered collection of unnamed elements.
+    Tuple {
+        type_name: Option<&'static str>,
+        elements: Vec<Tree>,
+    },
+    List(Vec<Tree>),
+    Map(Vec<(Tree, Tree)>),
+}
+
+impl Tree {
+    /// Gets a short description of the kind of value this is, for use in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Tree::Null => "null",
+            Tree::Bool(_) => "bool",
+            Tree::I8(_) => "i8",
+            Tree::I16(_) => "i16",
+            Tree::I32(_) => "i32",
+            Tree::I64(_) => "i64",
+            Tree::I128(_) => "i128",
+            Tree::U8(_) => "u8",
+            Tree::U16(_) => "u16",
+            Tree::U32(_) => "u32",
+            Tree::U64(_) => "u64",
+            Tree::U128(_) => "u128",
+            Tree::F32(_) => "f32",
+            Tree::F64(_) => "f64",
+            Tree::Char(_) => "char",
+            Tree::Str(_) => "string",
+            Tree::Bytes(_) => "bytes",
+            Tree::Tag { .. } => "tag",
+            Tree::Struct { .. } => "struct",
+            Tree::Tuple { .. } => "tuple",
+            Tree::List(_) => "list",
+            Tree::Map(_) => "map",
+        }
+    }
+}
+
+impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Tree {
+    const NULLABLE: bool = true;
+    fn serialize(&self, value: Value<S>, context: &mut Ctx) -> Result<(), S::Error> {
+        match self {
+            Tree::Null => {
+                if value.as_raw().supports_null() {
+                    let (s, done) = value.into_raw();
+                    s.pop_null()?;
+                    *done = true;
+                    Ok(())
+                } else {
+                    // Fallback to using the representation of `()`
+                    value.put_using(&(), context)
+                }
+            }
+            Tree::Bool(x) => value.put_bool(*x),
+            Tree::I8(x) => value.put_i8(*x),
+            Tree::I16(x) => value.put_i16(*x),
+            Tree::I32(x) => value.put_i32(*x),
+            Tree::I64(x) => value.put_i64(*x),
+            Tree::I128(x) => value.put_i128(*x),
+            Tree::U8(x) => value.put_u8(*x),
+            Tree::U16(x) => value.put_u16(*x),
+            Tree::U32(x) => value.put_u32(*x),
+            Tree::U64(x) => value.put_u64(*x),
+            Tree::U128(x) => value.put_u128(*x),
+            Tree::F32(x) => value.put_f32(*x),
+            Tree::F64(x) => value.put_f64(*x),
+            Tree::Char(x) => value.put_char(*x),
+            Tree::Str(x) => value.put_str(x),
+            Tree::Bytes(x) => value.put_bytes(x),
+            Tree::Tag {
+                max_index,
+                index,
+                name,
+            } => value.put_tag(*max_index, *index, *name),
+            Tree::Struct { type_name, fields } => {
+                let mut st = value.into_struct(*type_name, None)?;
+                for (name, field) in fields {
+                    st.field(name)?.put_using(field, context)?;
+                }
+                st.close()
+            }
+            Tree::Tuple {
+                type_name,
+                elements,
+            } => {
+                let mut tuple = value.into_tuple(*type_name)?;
+                for element in elements {
+                    tuple.element()?.put_using(element, context)?;
+                }
+                tuple.close()
+            }
+            Tree::List(items) => {
+                let mut list = value.into_list_sized(items.len())?;
+                for item in items {
+                    list.push()?.put_using(item, context)?;
+                }
+                list.close()
+            }
+            Tree::Map(entries) => {
+                let str_keys = entries
+                    .iter()
+                    .all(|(key, _)| matches!(key, Tree::Str(_) | Tree::Char(_)));
+                let mut map = value.into_map_sized(entries.len(), str_keys)?;
+                for (key, value) in entries {
+                    map.key()?.put_using(key, context)?;
+                    map.value()?.put_using(value, context)?;
+                }
+                map.close()
+            }
+        }
+    }
+}
+
+/// A [`Tree`] can be read from any [`ShapeDeserializer`]. Since the deserializer only reports the
+/// [`ValueKind`] of each value, the resulting tree only uses a subset of the possible variants:
+/// integers are read as [`Tree::U64`] or [`Tree::I64`] (or their 128-bit counterparts, if they
+/// don't fit), floating-point numbers as [`Tree::F64`], lists as [`Tree::List`] and structs as
+/// [`Tree::Map`].
+impl<D: ShapeDeserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for Tree {
+    const NULLABLE: bool = true;
+    fn deserialize(mut value: Value<D>, context: &mut Ctx) -> Result<Self, D::Error> {
+        Ok(match value.peek_kind()? {
+            ValueKind::Null => {
+                let (d, done_flag) = value.into_raw();
+                d.pop_null()?;
+                *done_flag = true;
+                Tree::Null
+            }
+            ValueKind::Bool => Tree::Bool(value.get_bool()?),
+            ValueKind::Integer | ValueKind::Float => {
+                let (d, done_flag) = value.into_raw();
+                let number = d.get_number()?;
+                *done_flag = true;
+                match number {
+                    Number::Unsigned(x) => match u64::try_from(x) {
+                        Ok(x) => Tree::U64(x),
+                        Err(_) => Tree::U128(x),
+                    },
+                    Number::Signed(x) => match i64::try_from(x) {
+                        Ok(x) => Tree::I64(x),
+                        Err(_) => Tree::I128(x),
+                    },
+                    Number::Float(x) => Tree::F64(x),
+                }
+            }
+            ValueKind::Str => Tree::Str(value.get_str()?.into_owned()),
+            ValueKind::Bytes => Tree::Bytes(value.get_bytes()?.into_owned()),
+            ValueKind::List => {
+                let mut items = Vec::new();
+                let mut list = value.into_list()?;
+                while let Some(item) = list.next()? {
+                    items.push(item.get_using(context)?);
+                }
+                Tree::List(items)
+            }
+            ValueKind::Struct => {
+                let mut entries = Vec::new();
+                let mut map = value.into_map()?;
+                while let Some(key) = map.next_key()? {
+                    let key = key.get_using(context)?;
+                    let value = map.value()?.get_using(context)?;
+                    entries.push((key, value));
+                }
+                Tree::Map(entries)
+            }
+            ValueKind::Unknown => panic!("{}", UNKNOWN_KIND_ERROR),
+        })
+    }
+}
+
+/// Converts a value of type `T` into a [`Tree`].
+pub fn to_tree<T: Serialize<TreeSerializer> + ?Sized>(value: &T) -> Result<Tree, TreeError> {
+    to_tree_using(value, &mut ())
+}
+
+/// Converts a value of type `T` into a [`Tree`].
+pub fn to_tree_using<T: Serialize<TreeSerializer, Ctx> + ?Sized, Ctx: ?Sized>(
+    value: &T,
+    context: &mut Ctx,
+) -> Result<Tree, TreeError> {
+    let mut s = TreeSerializer::new();
+    Value::with(&mut s, |v| value.serialize(v, context))?;
+    Ok(s.finish())
+}
+
+/// Reads a value of type `T` from a [`Tree`].
+pub fn from_tree<'t, T: Deserialize<TreeDeserializer<'t>>>(tree: &'t Tree) -> Result<T, TreeError> {
+    from_tree_using(tree, &mut ())
+}
+
+/// Reads a value of type `T` from a [`Tree`].
+pub fn from_tree_using<'t, T: Deserialize<TreeDeserializer<'t>, Ctx>, Ctx: ?Sized>(
+    tree: &'t Tree,
+    context: &mut Ctx,
+) -> Result<T, TreeError> {
+    let mut d = TreeDeserializer::new(tree);
+    Value::with(&mut d, |v| T::deserialize(v, context))
+}
+
+/// A [`Serializer`] which builds a [`Tree`]. Trees are treated as human-readable (see
+/// [`Outliner::is_human_readable`]), so they can be transcoded to and from text formats without
+/// changing representation.
+#[derive(Default)]
+pub struct TreeSerializer {
+    stack: Vec<TreeBuilder>,
+    result: Option<Tree>,
+}
+
+/// An opened item on the stack of a [`TreeSerializer`].
+enum TreeBuilder {
+    Str(String),
+    Bytes(Vec<u8>),
+    Struct {
+        type_name: Option<&'static str>,
+        fields: Vec<(&'static str, Tree)>,
+        field: Option<&'static str>,
+    },
+    Tuple {
+        type_name: Option<&'static str>,
+        elements: Vec<Tree>,
+    },
+    List(Vec<Tree>),
+    Map {
+        entries: Vec<(Tree, Tree)>,
+        key: Option<Tree>,
+    },
+}
+
+impl TreeSerializer {
+    /// Constructs a new [`TreeSerializer`] with a single value on its stack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the [`Tree`] for the value that was serialized, asserting that it is complete.
+    pub fn finish(self) -> Tree {
+        assert!(self.stack.is_empty(), "{}", INVALID_STATE_ERROR);
+        self.result.expect(INVALID_STATE_ERROR)
+    }
+
+    /// Assuming that the top item on the stack is a value, assigns it to the given [`Tree`].
+    fn put(&mut self, tree: Tree) -> Result<(), TreeError> {
+        match self.stack.last_mut() {
+            None => {
+                assert!(self.result.is_none(), "{}", INVALID_STATE_ERROR);
+                self.result = Some(tree);
+            }
+            Some(TreeBuilder::Struct { fields, field, .. }) => {
+                fields.push((field.take().expect(INVALID_STATE_ERROR), tree))
+            }
+            Some(TreeBuilder::Tuple { elements, .. }) => elements.push(tree),
+            Some(TreeBuilder::List(items)) => items.push(tree),
+            Some(TreeBuilder::Map { entries, key }) => match key.take() {
+                Some(key) => entries.push((key, tree)),
+                None => *key = Some(tree),
+            },
+            Some(TreeBuilder::Str(_) | TreeBuilder::Bytes(_)) => panic!("{}", INVALID_STATE_ERROR),
+        }
+        Ok(())
+    }
+
+    /// Pops the top item from the stack, which is expected to be an opened item.
+    fn pop(&mut self) -> TreeBuilder {
+        self.stack.pop().expect(INVALID_STATE_ERROR)
+    }
+}
+
+impl Outliner for TreeSerializer {
+    type Error = TreeError;
+
+    fn supports_null(&self) -> bool {
+        true
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.put(Tree::Null)
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        self.stack.push(TreeBuilder::Str(String::new()));
+        Ok(())
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        let TreeBuilder::Str(str) = self.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        self.put(Tree::Str(str))
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        self.stack.push(TreeBuilder::Bytes(Vec::new()));
+        Ok(())
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        let TreeBuilder::Bytes(bytes) = self.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        self.put(Tree::Bytes(bytes))
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        self.stack.push(TreeBuilder::Struct {
+            type_name,
+            fields: Vec::new(),
+            field: None,
+        });
+        Ok(())
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        let Some(TreeBuilder::Struct { field, .. }) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        *field = Some(name);
+        Ok(())
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        let TreeBuilder::Struct {
+            type_name, fields, ..
+        } = self.pop()
+        else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        self.put(Tree::Struct { type_name, fields })
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        self.stack.push(TreeBuilder::Tuple {
+            type_name,
+            elements: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        let TreeBuilder::Tuple {
+            type_name,
+            elements,
+        } = self.pop()
+        else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        self.put(Tree::Tuple {
+            type_name,
+            elements,
+        })
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        let TreeBuilder::List(items) = self.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        self.put(Tree::List(items))
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        let TreeBuilder::Map { entries, .. } = self.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        self.put(Tree::Map(entries))
+    }
+}
+
+impl Serializer for TreeSerializer {
+    fn put_bool(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.put(Tree::Bool(value))
+    }
+
+    fn put_i8(&mut self, value: i8) -> Result<(), Self::Error> {
+        self.put(Tree::I8(value))
+    }
+
+    fn put_i16(&mut self, value: i16) -> Result<(), Self::Error> {
+        self.put(Tree::I16(value))
+    }
+
+    fn put_i32(&mut self, value: i32) -> Result<(), Self::Error> {
+        self.put(Tree::I32(value))
+    }
+
+    fn put_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.put(Tree::I64(value))
+    }
+
+    fn put_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.put(Tree::I128(value))
+    }
+
+    fn put_u8(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.put(Tree::U8(value))
+    }
+
+    fn put_u16(&mut self, value: u16) -> Result<(), Self::Error> {
+        self.put(Tree::U16(value))
+    }
+
+    fn put_u32(&mut self, value: u32) -> Result<(), Self::Error> {
+        self.put(Tree::U32(value))
+    }
+
+    fn put_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.put(Tree::U64(value))
+    }
+
+    fn put_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.put(Tree::U128(value))
+    }
+
+    fn put_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        self.put(Tree::F32(value))
+    }
+
+    fn put_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.put(Tree::F64(value))
+    }
+
+    fn put_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.put(Tree::Char(value))
+    }
+
+    fn append_char(&mut self, value: char) -> Result<(), Self::Error> {
+        let Some(TreeBuilder::Str(str)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        str.push(value);
+        Ok(())
+    }
+
+    fn append_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        let Some(TreeBuilder::Str(str)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        str.push_str(value);
+        Ok(())
+    }
+
+    fn put_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.put(Tree::Str(value.to_owned()))
+    }
+
+    fn append_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        let Some(TreeBuilder::Bytes(bytes)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        bytes.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn put_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.put(Tree::Bytes(value.to_vec()))
+    }
+
+    fn put_tag(
+        &mut self,
+        max_index: usize,
+        index: usize,
+        name: Option<&'static str>,
+    ) -> Result<(), Self::Error> {
+        self.put(Tree::Tag {
+            max_index,
+            index,
+            name,
+        })
+    }
+
+    fn open_list_sized(&mut self, len: usize) -> Result<(), Self::Error> {
+        self.stack.push(TreeBuilder::List(Vec::with_capacity(len)));
+        Ok(())
+    }
+
+    fn open_map_sized(&mut self, len: usize, str_keys: bool) -> Result<(), Self::Error> {
+        let _ = str_keys;
+        self.stack.push(TreeBuilder::Map {
+            entries: Vec::with_capacity(len),
+            key: None,
+        });
+        Ok(())
+    }
+
+    fn supports_unsized(&self) -> bool {
+        true
+    }
+
+    fn open_list_unsized(&mut self) -> Result<(), Self::Error> {
+        self.open_list_sized(0)
+    }
+
+    fn open_map_unsized(&mut self, str_keys: bool) -> Result<(), Self::Error> {
+        self.open_map_sized(0, str_keys)
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        TreeError::Custom(source)
+    }
+}
+
+/// A [`Deserializer`] which reads from a [`Tree`]. Like [`TreeSerializer`], this is
+/// human-readable.
+///
+/// This is also a [`ShapeDeserializer`], so a [`Tree`] can be the source for [`crate::transcode`].
+/// To support this, tuples can be read as lists, structs can be read as maps with string keys,
+/// and tags can be read as their name (if they have one) or index. Conversely, so that a [`Tree`]
+/// read from a self-describing format can be read back as a typed value, maps with string keys
+/// can be read as structs (looking up fields by name) and lists can be read as tuples.
+pub struct TreeDeserializer<'t> {
+    stack: Vec<TreeReader<'t>>,
+
+    /// For each checkpoint taken using [`Deserializer::checkpoint`], the length of the stack at
+    /// the time it was taken, along with the value on top of it.
+    checkpoints: Vec<(usize, &'t Tree)>,
+}
+
+/// An item on the stack of a [`TreeDeserializer`].
+enum TreeReader<'t> {
+    Value(&'t Tree),
+    Str(core::str::Chars<'t>),
+    Bytes(Option<&'t [u8]>),
+
+    /// The name of a struct field, as a value which can only be read as a string.
+    Name(&'static str),
+
+    /// A [`Tree::Char`] which was opened as a string.
+    Char(Option<char>),
+    Struct(core::slice::Iter<'t, (&'static str, Tree)>),
+    Tuple(core::slice::Iter<'t, Tree>),
+    List(core::slice::Iter<'t, Tree>),
+    Map {
+        entries: core::slice::Iter<'t, (Tree, Tree)>,
+        value: Option<&'t Tree>,
+    },
+
+    /// A [`Tree::Map`] with string keys which was opened as a struct. Since fields are looked up
+    /// by name, this tracks which entries have been read.
+    Object {
+        entries: &'t [(Tree, Tree)],
+        read: Vec<bool>,
+    },
+
+    /// A [`Tree::Struct`] which was opened as a map.
+    Fields {
+        fields: core::slice::Iter<'t, (&'static str, Tree)>,
+        value: Option<&'t Tree>,
+    },
+}
+
+impl<'t> TreeDeserializer<'t> {
+    /// Constructs a new [`TreeDeserializer`] with the given [`Tree`] as the only value on its
+    /// stack.
+    pub fn new(tree: &'t Tree) -> Self {
+        Self {
+            stack: vec![TreeReader::Value(tree)],
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Assuming that the top item on the stack is a value, pops it and returns it as a [`Tree`].
+    pub fn get_tree(&mut self) -> &'t Tree {
+        let Some(TreeReader::Value(tree)) = self.stack.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        tree
+    }
+
+    /// Assuming that the top item on the stack is a value, pops it and returns it as a [`Tree`].
+    /// Fails if the value is the name of a struct field, which can only be read as a string.
+    fn pop_value(&mut self, expected: &'static str) -> Result<&'t Tree, TreeError> {
+        match self.stack.pop() {
+            Some(TreeReader::Value(tree)) => Ok(tree),
+            Some(TreeReader::Name(_)) => Err(TreeError::Expected {
+                expected,
+                found: "string",
+            }),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        }
+    }
+
+    /// Assuming that the top item on the stack is a value, pops it and interprets it as a
+    /// floating-point number.
+    fn get_float(&mut self, expected: &'static str) -> Result<f64, TreeError> {
+        let tree = self.pop_value(expected)?;
+        Ok(match *tree {
+            Tree::F32(x) => x.into(),
+            Tree::F64(x) => x,
+            Tree::I8(x) => x.into(),
+            Tree::I16(x) => x.into(),
+            Tree::I32(x) => x.into(),
+            Tree::I64(x) => x as f64,
+            Tree::I128(x) => x as f64,
+            Tree::U8(x) => x.into(),
+            Tree::U16(x) => x.into(),
+            Tree::U32(x) => x.into(),
+            Tree::U64(x) => x as f64,
+            Tree::U128(x) => x as f64,
+            _ => return Err(TreeError::expected(expected, tree)),
+        })
+    }
+}
+
+impl Outliner for TreeDeserializer<'_> {
+    type Error = TreeError;
+
+    fn supports_null(&self) -> bool {
+        true
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
+    fn pop_null(&mut self) -> Result<(), Self::Error> {
+        match self.pop_value("null")? {
+            Tree::Null => Ok(()),
+            tree => Err(TreeError::expected("null", tree)),
+        }
+    }
+
+    fn open_str(&mut self) -> Result<(), Self::Error> {
+        let reader = match self.stack.pop() {
+            Some(TreeReader::Name(name)) => TreeReader::Str(name.chars()),
+            Some(TreeReader::Value(tree)) => match tree {
+                Tree::Str(str) => TreeReader::Str(str.chars()),
+                Tree::Char(ch) => TreeReader::Char(Some(*ch)),
+                Tree::Tag {
+                    name: Some(name), ..
+                } => TreeReader::Str(name.chars()),
+                tree => return Err(TreeError::expected("string", tree)),
+            },
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        self.stack.push(reader);
+        Ok(())
+    }
+
+    fn close_str(&mut self) -> Result<(), Self::Error> {
+        let is_empty = match self.stack.pop() {
+            Some(TreeReader::Str(chars)) => chars.as_str().is_empty(),
+            Some(TreeReader::Char(ch)) => ch.is_none(),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        if is_empty {
+            Ok(())
+        } else {
+            Err(TreeError::ExcessChars)
+        }
+    }
+
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        match self.pop_value("bytes")? {
+            Tree::Bytes(bytes) => {
+                self.stack.push(TreeReader::Bytes(Some(bytes)));
+                Ok(())
+            }
+            tree => Err(TreeError::expected("bytes", tree)),
+        }
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        let Some(TreeReader::Bytes(rem)) = self.stack.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        if rem.is_none_or(|rem| rem.is_empty()) {
+            Ok(())
+        } else {
+            Err(TreeError::ExcessBytes)
+        }
+    }
+
+    fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let _ = type_name;
+        match self.pop_value("struct")? {
+            Tree::Struct { fields, .. } => {
+                self.stack.push(TreeReader::Struct(fields.iter()));
+                Ok(())
+            }
+            Tree::Map(entries) if entries.iter().all(|(key, _)| key_str(key).is_some()) => {
+                self.stack.push(TreeReader::Object {
+                    entries,
+                    read: vec![false; entries.len()],
+                });
+                Ok(())
+            }
+            tree => Err(TreeError::expected("struct", tree)),
+        }
+    }
+
+    fn push_field(&mut self, name: &'static str) -> Result<(), Self::Error> {
+        let fields = match self.stack.last_mut() {
+            Some(TreeReader::Struct(fields)) => fields,
+            Some(TreeReader::Object { entries, read }) => {
+                let entries = *entries;
+                let index = (0..entries.len())
+                    .find(|&i| !read[i] && key_str(&entries[i].0).is_some_and(|key| key == name))
+                    .ok_or(TreeError::MissingField(name))?;
+                read[index] = true;
+                self.stack.push(TreeReader::Value(&entries[index].1));
+                return Ok(());
+            }
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        match fields.next() {
+            Some((found, field)) if *found == name => {
+                self.stack.push(TreeReader::Value(field));
+                Ok(())
+            }
+            Some((found, _)) => Err(TreeError::UnexpectedField {
+                expected: name,
+                found,
+            }),
+            None => Err(TreeError::MissingField(name)),
+        }
+    }
+
+    fn close_struct(&mut self) -> Result<(), Self::Error> {
+        match self.stack.pop() {
+            Some(TreeReader::Struct(fields)) => {
+                if let Some((name, _)) = fields.as_slice().first() {
+                    Err(TreeError::ExtraField(name))
+                } else {
+                    Ok(())
+                }
+            }
+            Some(TreeReader::Object { entries, read }) => {
+                match entries.iter().zip(read).find(|(_, read)| !read) {
+                    Some(((key, _), _)) => {
+                        let key = key_str(key).expect(INVALID_STATE_ERROR);
+                        Err(TreeError::UnknownField(key.into_owned()))
+                    }
+                    None => Ok(()),
+                }
+            }
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        }
+    }
+
+    fn open_tuple(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
+        let _ = type_name;
+        match self.pop_value("tuple")? {
+            Tree::Tuple { elements, .. } | Tree::List(elements) => {
+                self.stack.push(TreeReader::Tuple(elements.iter()));
+                Ok(())
+            }
+            tree => Err(TreeError::expected("tuple", tree)),
+        }
+    }
+
+    fn push_element(&mut self) -> Result<(), Self::Error> {
+        let Some(TreeReader::Tuple(elements)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        let element = elements.next().ok_or(TreeError::MissingItems)?;
+        self.stack.push(TreeReader::Value(element));
+        Ok(())
+    }
+
+    fn close_tuple(&mut self) -> Result<(), Self::Error> {
+        let Some(TreeReader::Tuple(elements)) = self.stack.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        if elements.as_slice().is_empty() {
+            Ok(())
+        } else {
+            Err(TreeError::ExcessItems)
+        }
+    }
+
+    fn push_item(&mut self) -> Result<(), Self::Error> {
+        let Some(TreeReader::List(items)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        let item = items.next().ok_or(TreeError::MissingItems)?;
+        self.stack.push(TreeReader::Value(item));
+        Ok(())
+    }
+
+    fn close_list(&mut self) -> Result<(), Self::Error> {
+        let Some(TreeReader::List(items)) = self.stack.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        if items.as_slice().is_empty() {
+            Ok(())
+        } else {
+            Err(TreeError::ExcessItems)
+        }
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        if self.next_key()? {
+            Ok(())
+        } else {
+            Err(TreeError::MissingItems)
+        }
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        let Some(TreeReader::Map { value, .. } | TreeReader::Fields { value, .. }) =
+            self.stack.last_mut()
+        else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        let value = value.take().expect(INVALID_STATE_ERROR);
+        self.stack.push(TreeReader::Value(value));
+        Ok(())
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        let is_empty = match self.stack.pop() {
+            Some(TreeReader::Map { entries, .. }) => entries.as_slice().is_empty(),
+            Some(TreeReader::Fields { fields, .. }) => fields.as_slice().is_empty(),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        if is_empty {
+            Ok(())
+        } else {
+            Err(TreeError::ExcessItems)
+        }
+    }
+}
+
+impl Deserializer for TreeDeserializer<'_> {
+    fn get_bool(&mut self) -> Result<bool, Self::Error> {
+        match self.pop_value("bool")? {
+            Tree::Bool(x) => Ok(*x),
+            tree => Err(TreeError::expected("bool", tree)),
+        }
+    }
+
+    fn get_i8(&mut self) -> Result<i8, Self::Error> {
+        get_int(self.pop_value("i8")?, "i8")
+    }
+
+    fn get_i16(&mut self) -> Result<i16, Self::Error> {
+        get_int(self.pop_value("i16")?, "i16")
+    }
+
+    fn get_i32(&mut self) -> Result<i32, Self::Error> {
+        get_int(self.pop_value("i32")?, "i32")
+    }
+
+    fn get_i64(&mut self) -> Result<i64, Self::Error> {
+        get_int(self.pop_value("i64")?, "i64")
+    }
+
+    fn get_i128(&mut self) -> Result<i128, Self::Error> {
+        get_int(self.pop_value("i128")?, "i128")
+    }
+
+    fn get_u8(&mut self) -> Result<u8, Self::Error> {
+        get_int(self.pop_value("u8")?, "u8")
+    }
+
+    fn get_u16(&mut self) -> Result<u16, Self::Error> {
+        get_int(self.pop_value("u16")?, "u16")
+    }
+
+    fn get_u32(&mut self) -> Result<u32, Self::Error> {
+        get_int(self.pop_value("u32")?, "u32")
+    }
+
+    fn get_u64(&mut self) -> Result<u64, Self::Error> {
+        get_int(self.pop_value("u64")?, "u64")
+    }
+
+    fn get_u128(&mut self) -> Result<u128, Self::Error> {
+        get_int(self.pop_value("u128")?, "u128")
+    }
+
+    fn get_f32(&mut self) -> Result<f32, Self::Error> {
+        self.get_float("f32").map(|x| x as f32)
+    }
+
+    fn get_f64(&mut self) -> Result<f64, Self::Error> {
+        self.get_float("f64")
+    }
+
+    fn get_char(&mut self) -> Result<char, Self::Error> {
+        let tree = match self.stack.pop() {
+            Some(TreeReader::Value(tree)) => tree,
+            Some(TreeReader::Name(name)) => {
+                let mut chars = name.chars();
+                return match (chars.next(), chars.next()) {
+                    (Some(ch), None) => Ok(ch),
+                    _ => Err(TreeError::Expected {
+                        expected: "char",
+                        found: "string",
+                    }),
+                };
+            }
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        match tree {
+            Tree::Char(x) => Ok(*x),
+            Tree::Str(str) => {
+                let mut chars = str.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => Ok(ch),
+                    _ => Err(TreeError::expected("char", tree)),
+                }
+            }
+            _ => Err(TreeError::expected("char", tree)),
+        }
+    }
+
+    fn next_char(&mut self) -> Result<Option<char>, Self::Error> {
+        let res = match self.stack.last_mut() {
+            Some(TreeReader::Str(chars)) => chars.next(),
+            Some(TreeReader::Char(ch)) => ch.take(),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        if res.is_none() {
+            self.stack.pop();
+        }
+        Ok(res)
+    }
+
+    fn flush_str(&mut self) -> Result<Cow<'_, str>, Self::Error> {
+        match self.stack.pop() {
+            Some(TreeReader::Str(chars)) => Ok(Cow::Borrowed(chars.as_str())),
+            Some(TreeReader::Char(ch)) => Ok(Cow::Owned(ch.into_iter().collect())),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        }
+    }
+
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        let Some(TreeReader::Bytes(rem)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        if let Some(chunk) = rem.take().filter(|chunk| !chunk.is_empty()) {
+            return Ok(Some(chunk));
+        }
+        self.stack.pop();
+        Ok(None)
+    }
+
+    fn flush_bytes(&mut self) -> Result<Cow<'_, [u8]>, Self::Error> {
+        let Some(TreeReader::Bytes(rem)) = self.stack.pop() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        Ok(Cow::Borrowed(rem.unwrap_or_default()))
+    }
+
+    fn get_tag(
+        &mut self,
+        max_index: usize,
+        names: &'static NameMap<usize>,
+    ) -> Result<usize, Self::Error> {
+        let tree = match self.stack.pop() {
+            Some(TreeReader::Value(tree)) => tree,
+            Some(TreeReader::Name(name)) => {
+                return match names.get(name) {
+                    Some(index) => Ok(*index),
+                    None => Err(self.error_invalid_name(names)),
+                }
+            }
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        let index = match tree {
+            Tree::Tag { index, .. } => *index,
+            Tree::Str(name) => {
+                return match names.get(name) {
+                    Some(index) => Ok(*index),
+                    None => Err(self.error_invalid_name(names)),
+                }
+            }
+            _ => {
+                self.stack.push(TreeReader::Value(tree));
+                get_int(self.get_tree(), "tag").map_err(|err| match err {
+                    TreeError::Expected { found, .. } => TreeError::Expected {
+                        expected: "tag",
+                        found,
+                    },
+                    TreeError::OutOfRange => self.error_invalid_index(usize::MAX),
+                    err => err,
+                })?
+            }
+        };
+        if index <= max_index {
+            Ok(index)
+        } else {
+            Err(self.error_invalid_index(index))
+        }
+    }
+
+    fn check_null(&mut self) -> Result<bool, Self::Error> {
+        if let Some(TreeReader::Value(Tree::Null)) = self.stack.last() {
+            self.stack.pop();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        let tree = match self.stack.last() {
+            Some(TreeReader::Value(tree)) => tree,
+            Some(TreeReader::Name(_)) => return Ok(ValueKind::Str),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        Ok(match tree {
+            Tree::Null => ValueKind::Null,
+            Tree::Bool(_) => ValueKind::Bool,
+            Tree::I8(_)
+            | Tree::I16(_)
+            | Tree::I32(_)
+            | Tree::I64(_)
+            | Tree::I128(_)
+            | Tree::U8(_)
+            | Tree::U16(_)
+            | Tree::U32(_)
+            | Tree::U64(_)
+            | Tree::U128(_) => ValueKind::Integer,
+            Tree::F32(_) | Tree::F64(_) => ValueKind::Float,
+            Tree::Char(_) | Tree::Str(_) => ValueKind::Str,
+            Tree::Bytes(_) => ValueKind::Bytes,
+            Tree::Struct { .. } | Tree::Map(_) => ValueKind::Struct,
+            Tree::Tuple { .. } | Tree::List(_) => ValueKind::List,
+            Tree::Tag { name: Some(_), .. } => ValueKind::Str,
+            Tree::Tag { name: None, .. } => ValueKind::Integer,
+        })
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        let tree = match self.stack.last() {
+            Some(TreeReader::Value(tree)) => *tree,
+            Some(TreeReader::Name(_)) => return Ok(false),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        self.checkpoints.push((self.stack.len(), tree));
+        Ok(true)
+    }
+
+    fn rewind(&mut self) {
+        let (len, tree) = self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+        self.stack.truncate(len - 1);
+        self.stack.push(TreeReader::Value(tree));
+    }
+
+    fn release(&mut self) {
+        self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+    }
+
+    fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        match self.pop_value("list")? {
+            Tree::List(items)
+            | Tree::Tuple {
+                elements: items, ..
+            } => {
+                self.stack.push(TreeReader::List(items.iter()));
+                Ok(Some(items.len()))
+            }
+            tree => Err(TreeError::expected("list", tree)),
+        }
+    }
+
+    fn next_item(&mut self) -> Result<bool, Self::Error> {
+        let Some(TreeReader::List(items)) = self.stack.last_mut() else {
+            panic!("{}", INVALID_STATE_ERROR)
+        };
+        if let Some(item) = items.next() {
+            self.stack.push(TreeReader::Value(item));
+            Ok(true)
+        } else {
+            self.stack.pop();
+            Ok(false)
+        }
+    }
+
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        match self.pop_value("map")? {
+            Tree::Map(entries) => {
+                self.stack.push(TreeReader::Map {
+                    entries: entries.iter(),
+                    value: None,
+                });
+                Ok(Some(entries.len()))
+            }
+            Tree::Struct { fields, .. } => {
+                self.stack.push(TreeReader::Fields {
+                    fields: fields.iter(),
+                    value: None,
+                });
+                Ok(Some(fields.len()))
+            }
+            tree => Err(TreeError::expected("map", tree)),
+        }
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        let key = match self.stack.last_mut() {
+            Some(TreeReader::Map { entries, value }) => entries.next().map(|(k, v)| {
+                *value = Some(v);
+                TreeReader::Value(k)
+            }),
+            Some(TreeReader::Fields { fields, value }) => fields.next().map(|(name, v)| {
+                *value = Some(v);
+                TreeReader::Name(name)
+            }),
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        };
+        if let Some(key) = key {
+            self.stack.push(key);
+            Ok(true)
+        } else {
+            self.stack.pop();
+            Ok(false)
+        }
+    }
+
+    fn error(&self, source: Box<dyn core::error::Error + Send + Sync>) -> Self::Error {
+        TreeError::Custom(source)
+    }
+
+    fn error_missing_item(&self) -> Self::Error {
+        TreeError::MissingItems
+    }
+
+    fn error_extra_item(&self) -> Self::Error {
+        TreeError::ExcessItems
+    }
+}
+
+impl ShapeDeserializer for TreeDeserializer<'_> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        let signed = |x: i128| match u128::try_from(x) {
+            Ok(x) => Number::Unsigned(x),
+            Err(_) => Number::Signed(x),
+        };
+        let tree = self.pop_value("number")?;
+        Ok(match *tree {
+            Tree::I8(x) => signed(x.into()),
+            Tree::I16(x) => signed(x.into()),
+            Tree::I32(x) => signed(x.into()),
+            Tree::I64(x) => signed(x.into()),
+            Tree::I128(x) => signed(x),
+            Tree::U8(x) => Number::Unsigned(x.into()),
+            Tree::U16(x) => Number::Unsigned(x.into()),
+            Tree::U32(x) => Number::Unsigned(x.into()),
+            Tree::U64(x) => Number::Unsigned(x.into()),
+            Tree::U128(x) => Number::Unsigned(x),
+            Tree::F32(x) => Number::Float(x.into()),
+            Tree::F64(x) => Number::Float(x),
+            Tree::Tag {
+                index, name: None, ..
+            } => Number::Unsigned(index as u128),
+            _ => return Err(TreeError::expected("number", tree)),
+        })
+    }
+
+    fn has_str_keys(&self) -> bool {
+        match self.stack.last() {
+            Some(TreeReader::Map { entries, .. }) => entries
+                .as_slice()
+                .iter()
+                .all(|(key, _)| matches!(key, Tree::Str(_) | Tree::Char(_))),
+            Some(TreeReader::Fields { .. }) => true,
+            _ => panic!("{}", INVALID_STATE_ERROR),
+        }
+    }
+}
+
+/// Interprets a [`Tree`] as a string, if it is one.
+fn key_str(tree: &Tree) -> Option<Cow<'_, str>> {
+    match tree {
+        Tree::Str(str) => Some(Cow::Borrowed(str)),
+        Tree::Char(ch) => Some(Cow::Owned(String::from(*ch))),
+        _ => None,
+    }
+}
+
+/// Interprets a [`Tree`] as an integer of type `T`.
+fn get_int<T: TryFrom<i128> + TryFrom<u128>>(
+    tree: &Tree,
+    expected: &'static str,
+) -> Result<T, TreeError> {
+    let res = match *tree {
+        Tree::I8(x) => T::try_from(i128::from(x)).ok(),
+        Tree::I16(x) => T::try_from(i128::from(x)).ok(),
+        Tree::I32(x) => T::try_from(i128::from(x)).ok(),
+        Tree::I64(x) => T::try_from(i128::from(x)).ok(),
+        Tree::I128(x) => T::try_from(x).ok(),
+        Tree::U8(x) => T::try_from(u128::from(x)).ok(),
+        Tree::U16(x) => T::try_from(u128::from(x)).ok(),
+        Tree::U32(x) => T::try_from(u128::from(x)).ok(),
+        Tree::U64(x) => T::try_from(u128::from(x)).ok(),
+        Tree::U128(x) => T::try_from(x).ok(),
+        _ => return Err(TreeError::expected(expected, tree)),
+    };
+    res.ok_or(TreeError::OutOfRange)
+}
+
+/// An error that can occur when serializing to or deserializing from a [`Tree`].
+#[derive(thiserror::Error, Debug)]
+pub enum TreeError {
+    #[error("{0}")]
+    Custom(Box<dyn core::error::Error + Send + Sync>),
+    #[error("expected {expected}, found {found}")]
+    Expected {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("expected field {expected:?}, found {found:?}")]
+    UnexpectedField {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("missing field {0:?}")]
+    MissingField(&'static str),
+    #[error("unexpected field {0:?}")]
+    ExtraField(&'static str),
+    #[error("unexpected field {0:?}")]
+    UnknownField(String),
+    #[error("fewer items than expected")]
+    MissingItems,
+    #[error("more items than expected")]
+    ExcessItems,
+    #[error("string has unread characters")]
+    ExcessChars,
+    #[error("byte string has unread bytes")]
+    ExcessBytes,
+    #[error("value is out of range")]
+    OutOfRange,
+}
+
+impl TreeError {
+    /// Constructs an error which says that a value of the given kind was expected, but the given
+    /// [`Tree`] was found instead.
+    fn expected(expected: &'static str, found: &Tree) -> Self {
+        TreeError::Expected {
+            expected,
+            found: found.kind(),
+        }
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let value: (Vec<Option<u32>>, String, [char; 2]) =
+        (vec![Some(1), None], "hi".into(), ['a', 'b']);
+    let tree = to_tree(&value).unwrap();
+    assert_eq!(from_tree::<(Vec<Option<u32>>, String, [char; 2])>(&tree).unwrap(), value);
+    assert_eq!(
+        from_tree::<Tree>(&tree).unwrap(),
+        Tree::List(vec![
+            Tree::List(vec![Tree::U64(1), Tree::Null]),
+            Tree::Str("hi".into()),
+            Tree::List(vec![Tree::Str("a".into()), Tree::Str("b".into())])
+        ])
+    );
+    assert_eq!(to_tree(&tree).unwrap(), tree);
+    assert!(matches!(from_tree::<u8>(&Tree::U32(256)), Err(TreeError::OutOfRange)));
+    assert!(matches!(from_tree::<bool>(&Tree::Null), Err(TreeError::Expected { .. })));
+}
+
+#[test]
+fn test_speculate() {
+    let tree = to_tree(&(vec![1u32, 2], String::from("x"))).unwrap();
+    let mut d = crate::Checker::new(TreeDeserializer::new(&tree));
+    let res = Value::with(&mut d, |mut value| {
+        if let Some(res) = value.speculate(|value| value.get::<(u32, String)>())? {
+            return Ok(res);
+        }
+        let (items, name) = value.get::<(Vec<u32>, String)>()?;
+        Ok((items.len() as u32, name))
+    });
+    assert_eq!(res.unwrap(), (2, "x".into()));
+}
@@ -6,10 +6,10 @@ pub fn expand(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
     let mut ctx = DeserializeImplContext::new(input, &ser);
     Ok(match &input.data {
         syn::Data::Struct(st) => {
-            let fields = deserialize_fields(&mut ctx, &st.fields)?;
+            let (body, in_place_body) = deserialize_fields(&mut ctx, &st.fields, quote! { Self })?;
             let name = input.ident.to_string();
-            let body = quote! { Self #fields };
-            ctx.generate_struct(&name, body)
+            let fields = field_names(&st.fields)?;
+            ctx.generate_struct(&name, fields, body, Some(in_place_body))
         }
         syn::Data::Enum(en) => {
             let ser = ctx.ser;
@@ -47,7 +47,7 @@ pub fn expand(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
                                 )*
                                 index => {
                                     let err = de.error_invalid_index(index);
-                                    return ::std::result::Result::Err(err);
+                                    return ::core::result::Result::Err(err);
                                 }
                             }
                         }},
@@ -92,12 +92,15 @@ pub fn expand(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
                                 .unwrap(),
                             );
                         } else {
-                            let fields = deserialize_fields(&mut ctx, &v.fields)?;
-                            variant_body.push(quote! { Self::#variant_ident #fields });
+                            let ctor = quote! { Self::#variant_ident };
+                            let (body, _) = deserialize_fields(&mut ctx, &v.fields, ctor)?;
+                            variant_body.push(body);
                         }
                     }
+                    // The fields depend on the variant, so they can't be listed up front
                     ctx.generate_struct(
                         name.as_str(),
+                        quote! { ::core::option::Option::None },
                         quote! {{
                             const NAMES: &#ser::NameMap<usize> = #name_map;
                             let (de, done_flag) = st.field(#tag)?.into_raw();
@@ -109,10 +112,11 @@ pub fn expand(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
                                 )*
                                 index => {
                                     let err = de.error_invalid_index(index);
-                                    return ::std::result::Result::Err(err);
+                                    return ::core::result::Result::Err(err);
                                 }
                             }
                         }},
+                        None,
                     )
                 }
             }
@@ -185,8 +189,17 @@ impl<'a> DeserializeImplContext<'a> {
         }
     }
 
-    /// Generates a `DeserializeStruct` implementation.
-    pub fn generate_struct(self, name: &str, body: TokenStream) -> TokenStream {
+    /// Generates a `DeserializeStruct` implementation. `fields` is an expression for the names of
+    /// the fields of the struct, if known (see [`field_names`]). If `in_place_body` is provided,
+    /// it will be used to implement in-place deserialization into an existing value named
+    /// `place`.
+    pub fn generate_struct(
+        self,
+        name: &str,
+        fields: TokenStream,
+        body: TokenStream,
+        in_place_body: Option<TokenStream>,
+    ) -> TokenStream {
         let Self {
             ser,
             d_ty,
@@ -197,6 +210,33 @@ impl<'a> DeserializeImplContext<'a> {
             where_clause,
             ..
         } = self;
+        let (in_place, content_in_place) = match in_place_body {
+            Some(in_place_body) => (
+                quote! {
+                    fn deserialize_in_place(
+                        value: #ser::Value<#d_ty>,
+                        place: &mut Self,
+                        ctx: &mut #ctx_ty)
+                        -> ::core::result::Result<(), <#d_ty as #ser::Outliner>::Error>
+                    {
+                        #ser::deserialize::deserialize_struct_in_place(value, place, ctx,
+                            ::core::option::Option::Some(#name), #fields)
+                    }
+                },
+                quote! {
+                    fn deserialize_content_in_place(
+                        st: &mut #ser::Struct<#d_ty>,
+                        place: &mut Self,
+                        ctx: &mut #ctx_ty)
+                        -> ::core::result::Result<(), <#d_ty as #ser::Outliner>::Error>
+                    {
+                        #in_place_body
+                        ::core::result::Result::Ok(())
+                    }
+                },
+            ),
+            None => (TokenStream::new(), TokenStream::new()),
+        };
         quote! {
             #[automatically_derived]
             impl <#impl_generics_params> #ser::Deserialize<#d_ty, #ctx_ty>
@@ -208,8 +248,10 @@ impl<'a> DeserializeImplContext<'a> {
                     -> ::core::result::Result<Self, <#d_ty as #ser::Outliner>::Error>
                 {
                     #ser::deserialize::deserialize_struct(value, ctx,
-                        ::core::option::Option::Some(#name))
+                        ::core::option::Option::Some(#name), #fields)
                 }
+
+                #in_place
             }
 
             #[automatically_derived]
@@ -224,41 +266,89 @@ impl<'a> DeserializeImplContext<'a> {
                 {
                     ::core::result::Result::Ok(#body)
                 }
+
+                #content_in_place
             }
         }
     }
 }
 
 /// Generates code to deserialize the fields of a struct or enum variant from a `Struct` named
-/// `st`.
+/// `st`. Returns an expression which constructs the value using the given constructor path,
+/// along with statements which deserialize the fields in-place into an existing value named
+/// `place`.
+///
+/// Each field is read using `Struct::recover`, so that when errors are being collected, a
+/// failing field doesn't prevent the remaining fields from being checked.
 fn deserialize_fields(
     ctx: &mut DeserializeImplContext<'_>,
     fields: &syn::Fields,
-) -> syn::Result<TokenStream> {
+    ctor: TokenStream,
+) -> syn::Result<(TokenStream, TokenStream)> {
     Ok(match fields {
+        syn::Fields::Named(fields) if fields.named.is_empty() => {
+            (quote! { #ctor {} }, TokenStream::new())
+        }
         syn::Fields::Named(fields) => {
+            let mut locals = Vec::new();
+            let mut field_idents = Vec::new();
             let mut body = TokenStream::new();
-            for field in &fields.named {
+            let mut in_place_body = TokenStream::new();
+            for (i, field) in fields.named.iter().enumerate() {
                 let field_ident = field.ident.as_ref().unwrap();
                 let field_repr = FieldRepr::get(field)?;
-                let deserialize = field_repr.deserialize(ctx, &field.ty);
-                body.extend(quote! { #field_ident: #deserialize, });
+                let (deserialize, deserialize_in_place) =
+                    field_repr.deserialize(ctx, &field.ty, quote! { place.#field_ident });
+                let local = syn::Ident::new(&format!("field_{}", i), Span::call_site());
+                body.extend(quote! {
+                    let #local = st.recover(|st| {
+                        let field = #deserialize;
+                        ::core::result::Result::Ok(field)
+                    })?;
+                });
+                in_place_body.extend(quote! {
+                    failed |= st.recover(|st| {
+                        #deserialize_in_place
+                        ::core::result::Result::Ok(())
+                    })?.is_none();
+                });
+                locals.push(local);
+                field_idents.push(field_ident);
             }
-            quote! { { #body } }
+            (
+                quote! {{
+                    #body
+                    match (#(#locals,)*) {
+                        (#(::core::option::Option::Some(#locals),)*) => #ctor {
+                            #(#field_idents: #locals,)*
+                        },
+                        _ => return ::core::result::Result::Err(st.error_recovered()),
+                    }
+                }},
+                quote! {
+                    let mut failed = false;
+                    #in_place_body
+                    if failed {
+                        return ::core::result::Result::Err(st.error_recovered());
+                    }
+                },
+            )
         }
         syn::Fields::Unnamed(_) => todo!(),
-        syn::Fields::Unit => TokenStream::new(),
+        syn::Fields::Unit => (ctor, TokenStream::new()),
     })
 }
 
 impl FieldRepr {
     /// Generates the code to deserialize a field with this representation and adds the required
-    /// bounds to the `where` clause.
+    /// bounds to the `where` clause. Returns an expression for the value of the field, along with
+    /// a statement which deserializes the field in-place into `place`.
     fn deserialize(
         &self,
         ctx: &mut DeserializeImplContext<'_>,
         field_ty: &syn::Type,
-    ) -> TokenStream {
+        place: TokenStream,
+    ) -> (TokenStream, TokenStream) {
         let DeserializeImplContext {
             ser,
             d_ty,
@@ -267,6 +357,10 @@ impl FieldRepr {
             ..
         } = ctx;
         let mut des_ty = field_ty;
+
+        // In-place deserialization can only reuse the existing value if it is deserialized
+        // directly as the field type
+        let is_direct = self.proxy.is_none() && self.borrow.is_none() && !self.display_fromstr;
         match &self.location {
             FieldLocation::Inlined => {
                 let mut value = quote! { st.inline_get_using(ctx)? };
@@ -277,31 +371,91 @@ impl FieldRepr {
                     })
                     .unwrap(),
                 );
-                value
+                let in_place = if is_direct {
+                    quote! { st.inline_get_into_using(&mut #place, ctx)?; }
+                } else {
+                    quote! { #place = #value; }
+                };
+                (value, in_place)
             }
             FieldLocation::Named { name, use_default } => {
+                let get = if self.display_fromstr {
+                    quote! { get_parsed() }
+                } else if self.borrow.is_some() {
+                    quote! { get_borrowed_using(ctx) }
+                } else {
+                    quote! { get_using(ctx) }
+                };
                 let res = if *use_default {
-                    let mut value = quote! { value.get_using(ctx)? };
+                    let mut value = quote! { value.#get? };
                     apply_proxy(where_clause, &mut value, &mut des_ty, &self.proxy);
                     where_clause
                         .predicates
                         .push(syn::parse2(quote! { #field_ty: ::core::default::Default }).unwrap());
-                    quote! {{
-                        let mut value = st.field(#name)?;
-                        if value.check_null()? {
-                            <#field_ty as ::core::default::Default>::default()
-                        } else {
-                            #value
-                        }
-                    }}
+                    let in_place_value = if is_direct {
+                        quote! { value.get_into_using(&mut #place, ctx)?; }
+                    } else {
+                        quote! { #place = #value; }
+                    };
+                    (
+                        quote! {{
+                            let mut value = st.field(#name)?;
+                            if value.check_null()? {
+                                <#field_ty as ::core::default::Default>::default()
+                            } else {
+                                #value
+                            }
+                        }},
+                        quote! {{
+                            let mut value = st.field(#name)?;
+                            if value.check_null()? {
+                                #place = <#field_ty as ::core::default::Default>::default();
+                            } else {
+                                #in_place_value
+                            }
+                        }},
+                    )
                 } else {
-                    let mut value = quote! { st.field(#name)?.get_using(ctx)? };
+                    let mut value = quote! { st.field(#name)?.#get? };
                     apply_proxy(where_clause, &mut value, &mut des_ty, &self.proxy);
-                    value
+                    let in_place = if is_direct {
+                        quote! { st.field(#name)?.get_into_using(&mut #place, ctx)?; }
+                    } else {
+                        quote! { #place = #value; }
+                    };
+                    (value, in_place)
                 };
-                where_clause.predicates.push(
-                    syn::parse2(quote! { #des_ty: #ser::Deserialize<#d_ty, #ctx_ty> }).unwrap(),
-                );
+                if self.display_fromstr {
+                    where_clause.predicates.push(
+                        syn::parse2(quote! { #des_ty: ::core::str::FromStr }).unwrap(),
+                    );
+                    where_clause.predicates.push(
+                        syn::parse2(quote! {
+                            <#des_ty as ::core::str::FromStr>::Err:
+                                ::core::error::Error + ::core::marker::Send + ::core::marker::Sync
+                                + 'static
+                        })
+                        .unwrap(),
+                    );
+                } else if let Some(lifetime) = &self.borrow {
+                    where_clause.predicates.push(
+                        syn::parse2(quote! {
+                            #d_ty: #ser::deserialize::BorrowDeserializer<#lifetime>
+                        })
+                        .unwrap(),
+                    );
+                    where_clause.predicates.push(
+                        syn::parse2(quote! {
+                            #des_ty: #ser::deserialize::BorrowDeserialize<#lifetime, #d_ty, #ctx_ty>
+                        })
+                        .unwrap(),
+                    );
+                } else {
+                    where_clause.predicates.push(
+                        syn::parse2(quote! { #des_ty: #ser::Deserialize<#d_ty, #ctx_ty> })
+                            .unwrap(),
+                    );
+                }
                 res
             }
         }
@@ -25,18 +25,6 @@ pub fn derive_deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenSt
 /// The default name for the field which contains the tag for an enum.
 const DEFAULT_TAG: &str = "type";
 
-/// Describes how a struct is represented during serialization and deserialization.
-enum StructRepr {
-    /// Serialization and deserialization is deferred to the struct's sole field.
-    Transparent,
-
-    /// The struct is serialized and deserialized as a `Struct`.
-    Struct {
-        /// The name of the `Struct`.
-        name: String,
-    },
-}
-
 /// Describes how an enum is represented during serialization and deserialization.
 enum EnumRepr {
     /// The "enum" is serialized and deserialized as a single tag.
@@ -70,6 +58,14 @@ struct FieldRepr {
     /// Specifies a "proxy" type that the field is serialized and/or deserialized.
     proxy: Option<syn::Type>,
 
+    /// If not [`None`], the field is deserialized using `BorrowDeserialize`, borrowing data from
+    /// the input for the given lifetime.
+    borrow: Option<syn::Lifetime>,
+
+    /// If `true`, the field is serialized as a string using its `Display` implementation, and
+    /// deserialized by parsing a string using its `FromStr` implementation.
+    display_fromstr: bool,
+
     /// The location of the data for this field in its serialized form.
     location: FieldLocation,
 }
@@ -208,6 +204,8 @@ impl FieldRepr {
         let mut rename = None;
         let mut proxy = None;
         let mut use_default = false;
+        let mut borrow = None;
+        let mut display_fromstr = false;
         for attr in field.attrs.iter() {
             if attr.path().is_ident("serde") {
                 attr.parse_nested_meta(|meta| {
@@ -221,6 +219,17 @@ impl FieldRepr {
                         proxy = Some(ty);
                     } else if meta.path.is_ident("default") {
                         use_default = true;
+                    } else if meta.path.is_ident("borrow") {
+                        borrow = Some(if meta.input.peek(syn::Token![=]) {
+                            let lit: syn::LitStr = meta.value()?.parse()?;
+                            lit.parse()?
+                        } else {
+                            find_lifetime(field.ty.to_token_stream()).ok_or_else(|| {
+                                meta.error("borrowed field must have a lifetime in its type")
+                            })?
+                        });
+                    } else if meta.path.is_ident("display_fromstr") {
+                        display_fromstr = true;
                     } else {
                         let path = meta.path.to_token_stream().to_string().replace(' ', "");
                         return Err(
@@ -231,8 +240,22 @@ impl FieldRepr {
                 })?;
             }
         }
+        if is_inlined && borrow.is_some() {
+            return Err(syn::Error::new(
+                field.span(),
+                "flattened fields can't be borrowed",
+            ));
+        }
+        if display_fromstr && (is_inlined || proxy.is_some() || borrow.is_some()) {
+            return Err(syn::Error::new(
+                field.span(),
+                "`display_fromstr` can't be combined with `flatten`, `proxy` or `borrow`",
+            ));
+        }
         Ok(FieldRepr {
             proxy,
+            borrow,
+            display_fromstr,
             location: if is_inlined {
                 // TODO: Check for incompatible attributes
                 FieldLocation::Inlined
@@ -250,4 +273,41 @@ impl FieldRepr {
             },
         })
     }
-}
\ No newline at end of file
+}
+
+/// Gets an expression for the names of the fields of a struct, as given to `Value::into_struct`.
+/// This is `None` if the fields can't be listed up front because some of them are flattened.
+fn field_names(fields: &syn::Fields) -> syn::Result<TokenStream> {
+    let mut names = Vec::new();
+    for field in fields.iter() {
+        match FieldRepr::get(field)?.location {
+            FieldLocation::Inlined => return Ok(quote! { ::core::option::Option::None }),
+            FieldLocation::Named { name, .. } => names.push(name),
+        }
+    }
+    Ok(quote! { ::core::option::Option::Some(&[#(#names),*]) })
+}
+
+/// Finds the first lifetime that appears in the given tokens.
+fn find_lifetime(tokens: TokenStream) -> Option<syn::Lifetime> {
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(token) = iter.next() {
+        match token {
+            proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(proc_macro2::TokenTree::Ident(ident)) = iter.peek() {
+                    return Some(syn::Lifetime {
+                        apostrophe: punct.span(),
+                        ident: ident.clone(),
+                    });
+                }
+            }
+            proc_macro2::TokenTree::Group(group) => {
+                if let Some(lifetime) = find_lifetime(group.stream()) {
+                    return Some(lifetime);
+                }
+            }
+            _ => (),
+        }
+    }
+    None
+}
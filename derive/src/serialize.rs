@@ -8,8 +8,10 @@ pub fn expand(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
         syn::Data::Struct(st) => {
             let (fields, body) = serialize_fields(&mut ctx, &st.fields)?;
             let name = input.ident.to_string();
+            let field_names = field_names(&st.fields)?;
             ctx.generate_struct(
                 &name,
+                field_names,
                 quote! {
                     let Self #fields = self;
                     #body
@@ -91,8 +93,10 @@ pub fn expand(input: &mut syn::DeriveInput) -> syn::Result<TokenStream> {
                             }
                         });
                     }
+                    // The fields depend on the variant, so they can't be listed up front
                     ctx.generate_struct(
                         name.as_str(),
+                        quote! { ::core::option::Option::None },
                         quote! {
                             match self {
                                 #(#variant_arm),*
@@ -171,8 +175,14 @@ impl<'a> SerializeImplContext<'a> {
         }
     }
 
-    /// Generates a `SerializeStruct` implementation.
-    pub fn generate_struct(self, name: &str, body: TokenStream) -> TokenStream {
+    /// Generates a `SerializeStruct` implementation. `fields` is an expression for the names of
+    /// the fields of the struct, if known (see [`field_names`]).
+    pub fn generate_struct(
+        self,
+        name: &str,
+        fields: TokenStream,
+        body: TokenStream,
+    ) -> TokenStream {
         let Self {
             ser,
             s_ty,
@@ -194,7 +204,7 @@ impl<'a> SerializeImplContext<'a> {
                     -> ::core::result::Result<(), <#s_ty as #ser::Outliner>::Error>
                 {
                     #ser::serialize::serialize_struct(value, self, ctx,
-                        ::core::option::Option::Some(#name))
+                        ::core::option::Option::Some(#name), #fields)
                 }
             }
 
@@ -257,7 +267,7 @@ impl FieldRepr {
             where_clause,
             ..
         } = ctx;
-        if let Some(proxy_ty) = &self.proxy {
+        if self.proxy.is_some() {
             todo!()
         }
         match &self.location {
@@ -270,6 +280,12 @@ impl FieldRepr {
                 );
                 quote! { st.inline_put_using(#value, ctx)?; }
             }
+            FieldLocation::Named { name, .. } if self.display_fromstr => {
+                where_clause
+                    .predicates
+                    .push(syn::parse2(quote! { #field_ty: ::core::fmt::Display }).unwrap());
+                quote! { st.field(#name)?.put_display(#value)?; }
+            }
             FieldLocation::Named { name, .. } => {
                 where_clause.predicates.push(
                     syn::parse2(quote! { #field_ty: #ser::Serialize<#s_ty, #ctx_ty> }).unwrap(),
@@ -0,0 +1,229 @@
+use serdere::TextWriter;
+
+/// Identifies a scheme for encoding byte strings as JSON strings.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum BytesEncoding {
+    /// The standard base64 alphabet, as defined by RFC 4648, with `=` padding.
+    #[default]
+    Base64,
+
+    /// The URL- and filename-safe base64 alphabet, as defined by RFC 4648, without padding.
+    Base64Url,
+
+    /// Lowercase hexadecimal, using two digits per byte.
+    Hex,
+}
+
+/// The alphabet for [`BytesEncoding::Base64`].
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The alphabet for [`BytesEncoding::Base64Url`].
+const BASE64_URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The alphabet for [`BytesEncoding::Hex`].
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+/// Encodes a byte string incrementally, writing the encoded characters to a [`TextWriter`].
+pub(crate) struct BytesEncoder {
+    encoding: BytesEncoding,
+
+    /// The bytes of an incomplete base64 group that have not been written yet.
+    pending: [u8; 2],
+    pending_len: u8,
+}
+
+impl BytesEncoder {
+    /// Constructs a new [`BytesEncoder`] for the given encoding.
+    pub fn new(encoding: BytesEncoding) -> Self {
+        Self {
+            encoding,
+            pending: [0; 2],
+            pending_len: 0,
+        }
+    }
+
+    /// Encodes the given bytes, writing all complete groups to `writer`.
+    pub fn write<Writer: TextWriter + ?Sized>(
+        &mut self,
+        writer: &mut Writer,
+        mut bytes: &[u8],
+    ) -> Result<(), Writer::Error> {
+        let alphabet = match self.encoding {
+            BytesEncoding::Base64 => BASE64_ALPHABET,
+            BytesEncoding::Base64Url => BASE64_URL_ALPHABET,
+            BytesEncoding::Hex => {
+                for byte in bytes {
+                    writer.write_char(HEX_ALPHABET[usize::from(byte >> 4)] as char)?;
+                    writer.write_char(HEX_ALPHABET[usize::from(byte & 0xF)] as char)?;
+                }
+                return Ok(());
+            }
+        };
+
+        // Complete the pending group
+        while self.pending_len > 0 {
+            let Some((&byte, rem)) = bytes.split_first() else {
+                return Ok(());
+            };
+            bytes = rem;
+            if self.pending_len < 2 {
+                self.pending[usize::from(self.pending_len)] = byte;
+                self.pending_len += 1;
+            } else {
+                self.pending_len = 0;
+                write_base64_group(writer, alphabet, [self.pending[0], self.pending[1], byte])?;
+            }
+        }
+
+        // Write full groups
+        let mut groups = bytes.chunks_exact(3);
+        for group in &mut groups {
+            write_base64_group(writer, alphabet, [group[0], group[1], group[2]])?;
+        }
+        let rem = groups.remainder();
+        self.pending[..rem.len()].copy_from_slice(rem);
+        self.pending_len = rem.len() as u8;
+        Ok(())
+    }
+
+    /// Writes the final incomplete group for the byte string, if any.
+    pub fn finish<Writer: TextWriter + ?Sized>(
+        &mut self,
+        writer: &mut Writer,
+    ) -> Result<(), Writer::Error> {
+        let (alphabet, pad) = match self.encoding {
+            BytesEncoding::Base64 => (BASE64_ALPHABET, true),
+            BytesEncoding::Base64Url => (BASE64_URL_ALPHABET, false),
+            BytesEncoding::Hex => return Ok(()),
+        };
+        let len = usize::from(std::mem::take(&mut self.pending_len));
+        if len > 0 {
+            let data = u32::from(self.pending[0]) << 16 | u32::from(self.pending[1]) << 8;
+            let data = if len < 2 { data & 0xFF0000 } else { data };
+            for i in 0..=len {
+                writer.write_char(alphabet[(data >> (18 - 6 * i) & 0x3F) as usize] as char)?;
+            }
+            if pad {
+                for _ in len..3 {
+                    writer.write_char('=')?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a complete group of 3 bytes in base64.
+fn write_base64_group<Writer: TextWriter + ?Sized>(
+    writer: &mut Writer,
+    alphabet: &[u8; 64],
+    group: [u8; 3],
+) -> Result<(), Writer::Error> {
+    let data = u32::from(group[0]) << 16 | u32::from(group[1]) << 8 | u32::from(group[2]);
+    for i in 0..4 {
+        writer.write_char(alphabet[(data >> (18 - 6 * i) & 0x3F) as usize] as char)?;
+    }
+    Ok(())
+}
+
+/// Decodes a byte string incrementally from its encoded characters.
+pub(crate) struct BytesDecoder {
+    encoding: BytesEncoding,
+
+    /// The bits of the current incomplete group.
+    data: u32,
+
+    /// The number of characters in the current incomplete group.
+    len: u8,
+
+    /// The number of padding characters that have been read.
+    padding: u8,
+}
+
+impl BytesDecoder {
+    /// Constructs a new [`BytesDecoder`] for the given encoding.
+    pub fn new(encoding: BytesEncoding) -> Self {
+        Self {
+            encoding,
+            data: 0,
+            len: 0,
+            padding: 0,
+        }
+    }
+
+    /// Decodes the given character, appending any completed bytes to `output`. Returns `false`
+    /// if the character is not valid at this point in the encoding.
+    pub fn push(&mut self, ch: char, output: &mut Vec<u8>) -> bool {
+        if let BytesEncoding::Hex = self.encoding {
+            let Some(digit) = ch.to_digit(16) else {
+                return false;
+            };
+            self.data = self.data << 4 | digit;
+            self.len += 1;
+            if self.len == 2 {
+                output.push(self.data as u8);
+                self.data = 0;
+                self.len = 0;
+            }
+            return true;
+        }
+
+        // Base64 (accepting either alphabet)
+        if ch == '=' {
+            if self.len < 2 || self.len + self.padding >= 4 {
+                return false;
+            }
+            self.padding += 1;
+            return true;
+        }
+        if self.padding > 0 {
+            return false;
+        }
+        let digit = match ch {
+            'A'..='Z' => ch as u32 - 'A' as u32,
+            'a'..='z' => ch as u32 - 'a' as u32 + 26,
+            '0'..='9' => ch as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            _ => return false,
+        };
+        self.data = self.data << 6 | digit;
+        self.len += 1;
+        if self.len == 4 {
+            output.extend_from_slice(&self.data.to_be_bytes()[1..]);
+            self.data = 0;
+            self.len = 0;
+        }
+        true
+    }
+
+    /// Completes decoding, appending any remaining bytes to `output`. Returns `false` if the
+    /// encoded data ended prematurely.
+    pub fn finish(&mut self, output: &mut Vec<u8>) -> bool {
+        let len = std::mem::take(&mut self.len);
+        let padding = std::mem::take(&mut self.padding);
+        let data = std::mem::take(&mut self.data);
+        match self.encoding {
+            BytesEncoding::Hex => len == 0,
+            BytesEncoding::Base64 | BytesEncoding::Base64Url => {
+                if padding > 0 && len + padding != 4 {
+                    return false;
+                }
+                match len {
+                    0 => true,
+                    2 => {
+                        output.push((data >> 4) as u8);
+                        true
+                    }
+                    3 => {
+                        output.extend_from_slice(&((data >> 2) as u16).to_be_bytes());
+                        true
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+}
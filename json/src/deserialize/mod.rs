@@ -227,3 +227,47 @@ pub fn from_str_using<'s, T: Deserialize<TextDeserializer<&'s str>, Ctx>, Ctx: ?
 ) -> Result<T, DeserializeError<StrPosition<'s>>> {
     from_reader_using(str, context)
 }
+
+/// Deserializes a value of type `T` from a [`TextReader`], interpreting the text as JSON, and
+/// stores it in `place`. This may reuse resources (e.g. allocations) held by the existing value.
+pub fn from_reader_into<Reader: TextReader, T: Deserialize<TextDeserializer<Reader>>>(
+    reader: Reader,
+    place: &mut T,
+) -> Result<(), DeserializeError<Reader::Position>> {
+    from_reader_into_using(reader, place, &mut ())
+}
+
+/// Deserializes a value of type `T` from a [`TextReader`], interpreting the text as JSON, and
+/// stores it in `place`. This may reuse resources (e.g. allocations) held by the existing value.
+pub fn from_reader_into_using<
+    Reader: TextReader,
+    T: Deserialize<TextDeserializer<Reader>, Ctx>,
+    Ctx: ?Sized,
+>(
+    reader: Reader,
+    place: &mut T,
+    context: &mut Ctx,
+) -> Result<(), DeserializeError<Reader::Position>> {
+    let mut d = TextDeserializer::new(TextDeserializerConfig::default(), reader)?;
+    Value::with(&mut d, |value| T::deserialize_in_place(value, place, context))?;
+    d.close()
+}
+
+/// Deserializes a value of type `T` from a string, interpreting it as JSON, and stores it in
+/// `place`. This may reuse resources (e.g. allocations) held by the existing value.
+pub fn from_str_into<'s, T: Deserialize<TextDeserializer<&'s str>>>(
+    str: &'s str,
+    place: &mut T,
+) -> Result<(), DeserializeError<StrPosition<'s>>> {
+    from_str_into_using(str, place, &mut ())
+}
+
+/// Deserializes a value of type `T` from a string, interpreting it as JSON, and stores it in
+/// `place`. This may reuse resources (e.g. allocations) held by the existing value.
+pub fn from_str_into_using<'s, T: Deserialize<TextDeserializer<&'s str>, Ctx>, Ctx: ?Sized>(
+    str: &'s str,
+    place: &mut T,
+    context: &mut Ctx,
+) -> Result<(), DeserializeError<StrPosition<'s>>> {
+    from_reader_into_using(str, place, context)
+}
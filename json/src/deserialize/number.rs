@@ -14,6 +14,10 @@ pub trait NumBuilder {
     /// Attempts to "append" a digit to the number, returning `false` if this would result in
     /// overflow
     fn push_digit(&mut self, digit: u8) -> bool;
+
+    /// Indicates that the number was written with a fractional part or an exponent, i.e. it is
+    /// not an integer literal. By default, this does nothing.
+    fn mark_non_integer(&mut self) {}
 }
 
 macro_rules! impl_unsigned {
@@ -183,4 +187,48 @@ macro_rules! impl_float {
 }
 
 impl_float!(f32);
-impl_float!(f64);
\ No newline at end of file
+impl_float!(f64);
+
+/// The [`NumBuilder`] for a [`serdere::Number`]. This tracks both an integer representation
+/// (until it overflows) and a floating-point representation of the number.
+#[derive(Default)]
+pub struct NumberBuilder {
+    int: u128,
+    int_overflow: bool,
+    non_integer: bool,
+    float: FloatBuilder,
+}
+
+impl NumBuilder for NumberBuilder {
+    fn push_digit(&mut self, digit: u8) -> bool {
+        if !self.int_overflow && !self.int.push_digit(digit) {
+            self.int_overflow = true;
+        }
+        self.float.push_digit(digit)
+    }
+
+    fn mark_non_integer(&mut self) {
+        self.non_integer = true;
+    }
+}
+
+/// A [`serdere::Number`] is read as an integer when it is written as an integer literal (without
+/// a fractional part or exponent) that fits in an [`i128`] or [`u128`], preferring
+/// [`serdere::Number::Unsigned`] for non-negative values. Otherwise, it is read as a
+/// floating-point number, preserving the sign of zero.
+impl Num for serdere::Number {
+    type Builder = NumberBuilder;
+    fn from_builder(builder: NumberBuilder, negate: bool, exp_10: i32) -> Option<Self> {
+        use serdere::Number;
+        if !builder.non_integer && !builder.int_overflow {
+            if let Some(value) = u128::from_builder(builder.int, false, exp_10) {
+                if !negate || value == 0 {
+                    return Some(Number::Unsigned(value));
+                } else if let Some(value) = i128::checked_sub_unsigned(0, value) {
+                    return Some(Number::Signed(value));
+                }
+            }
+        }
+        f64::from_builder(builder.float, negate, exp_10).map(Number::Float)
+    }
+}
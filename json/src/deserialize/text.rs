@@ -1,7 +1,11 @@
 use super::number::{Num, NumBuilder};
-use crate::{CollectionType, ValueType};
+use crate::bytes::BytesDecoder;
+use crate::{BytesEncoding, CollectionType, ValueType};
 use crate::{JsonDeserializer, JsonOutliner};
-use serdere::{prefix, Deserializer, NameMap, Outliner, TextReader};
+use serdere::deserialize::{BorrowDeserializer, NO_CHECKPOINT_ERROR};
+use serdere::{prefix, Deserializer, NameMap, Number, Outliner, ShapeDeserializer};
+use serdere::{Erase, ErasedError, TextReader, ValueKind};
+use std::borrow::Cow;
 use std::hash::{BuildHasher, Hasher};
 use std::num::NonZeroU32;
 use DeserializeErrorMessage::*;
@@ -13,6 +17,12 @@ pub struct TextDeserializer<Reader: TextReader> {
     outline: Outline<Reader::Position>,
     state: DeserializerState,
     error_pos: Reader::Position,
+    bytes: BytesDecoder,
+    bytes_buf: Vec<u8>,
+    bytes_at_end: bool,
+    maps: Vec<MapFrame>,
+    at_map_key: bool,
+    checkpoints: Vec<Checkpoint<Reader::Position>>,
 }
 
 /// Encapsulates the configuration options for a [`TextDeserializer`].
@@ -20,6 +30,9 @@ pub struct TextDeserializer<Reader: TextReader> {
 pub struct TextDeserializerConfig {
     /// Indicates whether the parser accepts JS-style comments where whitespace is expected.
     pub allow_comments: bool,
+
+    /// The encoding expected for byte strings.
+    pub bytes_encoding: BytesEncoding,
     // TODO: Allow trailing comma
 }
 
@@ -28,6 +41,7 @@ impl TextDeserializerConfig {
     pub const fn strict() -> Self {
         Self {
             allow_comments: false,
+            bytes_encoding: BytesEncoding::Base64,
         }
     }
 
@@ -35,6 +49,7 @@ impl TextDeserializerConfig {
     pub const fn permissive() -> Self {
         Self {
             allow_comments: true,
+            bytes_encoding: BytesEncoding::Base64,
         }
     }
 }
@@ -95,7 +110,7 @@ struct LookbackItem<Position> {
 
 /// Describes a value that has been read, but not yet returned by a [`TextDeserializer`].
 /// Supplementary data may be available in `lookback_data`.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum LookbackValue {
     /// A JSON string. The string data comes from the slice of `lookback_data` between the end
     /// of the entry key and the start of the data for the next item.
@@ -110,6 +125,9 @@ enum LookbackValue {
 
         /// The base-10 exponent for this number.
         exp: i16,
+
+        /// Is the number written as an integer literal, without a fractional part or exponent?
+        integer: bool,
     },
 
     /// A JSON object. The entry data comes from the following items in `lookback_items`.
@@ -132,6 +150,7 @@ enum LookbackValue {
 }
 
 /// Describes the overall state of a [`TextDeserializer`].
+#[derive(Clone, Copy)]
 enum DeserializerState {
     /// There is a value at the top of the deserialization stack and `reader` is positioned at
     /// the first character for the value.
@@ -187,12 +206,56 @@ enum DeserializerState {
         /// stack.
         value_index: Option<usize>,
 
+        /// If not [`None`], indicates that this string is the value for the specified item in
+        /// `lookback_items`. The position of that item can be used to locate the original string
+        /// in the input.
+        source_index: Option<usize>,
+
         /// The depth of the first opened collection in the deserialization stack that has not
         /// yet been fully read.
         streaming_depth: Option<NonZeroU32>,
     },
 }
 
+/// Describes how an opened map on the deserialization stack is represented in JSON.
+enum MapFrame {
+    /// The map is an object. Its keys are the object keys, which are always strings.
+    Object {
+        /// The number of items in `stack_items` while the map is on top of the stack.
+        depth: usize,
+    },
+
+    /// The map is an array of `[key, value]` pairs.
+    Array {
+        /// The number of items in `stack_items` while the map is on top of the stack.
+        depth: usize,
+
+        /// Indicates whether the pair for the current entry is open on the stack.
+        pair_open: bool,
+    },
+}
+
+/// The state saved by [`Deserializer::checkpoint`], which is restored by
+/// [`Deserializer::rewind`]. Since the value for the checkpoint is always read into
+/// `lookback_items` before it is returned, reading it only affects the stack items, maps and
+/// lookback keys above it, along with the values of the lookback items for it.
+struct Checkpoint<Position> {
+    /// The state of the deserializer when the checkpoint was taken. This is either
+    /// [`DeserializerState::LookbackValue`] or [`DeserializerState::NullValue`].
+    state: DeserializerState,
+    error_pos: Position,
+
+    /// The number of items in `stack_items` when the checkpoint was taken.
+    depth: usize,
+
+    /// The number of items in `maps` when the checkpoint was taken.
+    num_maps: usize,
+
+    /// The values of the lookback items for the value, starting at the item for the value
+    /// itself.
+    values: Vec<Option<LookbackValue>>,
+}
+
 /// Identifies a entry key that belongs to a entry in `lookback_items` for an object that is
 /// currently opened on the stack.
 struct LookbackKey {
@@ -203,6 +266,9 @@ struct LookbackKey {
     index: usize,
 }
 
+/// The maximum number of bytes returned by one call to [`Deserializer::next_bytes_chunk`].
+const BYTES_CHUNK_LEN: usize = 1024;
+
 /// An error message that indicates that a value in [`LookbackItem`]s has already been read.
 const VALUE_ALREADY_READ: &str = "value already read";
 
@@ -222,6 +288,10 @@ const NOT_OBJECT: &str = "top of the deserialization stack is not an opened obje
 /// be an opened array, but it isn't.
 const NOT_ARRAY: &str = "top of the deserialization stack is not an opened array";
 
+/// An error message that indicates that the top of the deserialization stack was expected to
+/// be an opened map, but it isn't.
+const NOT_MAP: &str = "top of the deserialization stack is not an opened map";
+
 /// An error message that indicates that the top of the deserialization stack was expected to
 /// be an opened string, but it isn't.
 const NOT_STRING: &str = "top of the deserialization stack is not an opened string";
@@ -241,12 +311,20 @@ impl<Reader: TextReader> TextDeserializer<Reader> {
             outline: Outline::default(),
             state: DeserializerState::StreamingValue,
             error_pos,
+            bytes: BytesDecoder::new(config.bytes_encoding),
+            bytes_buf: Vec::new(),
+            bytes_at_end: false,
+            maps: Vec::new(),
+            at_map_key: false,
+            checkpoints: Vec::new(),
         })
     }
 
     /// Assuming that the top item on the stack is a value, pops it from the stack and returns it,
     /// interpreting it as a number.
     pub fn read_number<T: Num>(&mut self) -> Result<T, DeserializeError<Reader::Position>> {
+        self.check_map_key(&[ValueType::Number])?;
+        self.check_value_type(&[ValueType::Number])?;
         match self.state {
             DeserializerState::StreamingValue => {
                 self.state = DeserializerState::Collection {
@@ -261,7 +339,12 @@ impl<Reader: TextReader> TextDeserializer<Reader> {
                 streaming_depth,
             } => {
                 let (pos, value, data) = self.outline.take_value(index);
-                if let LookbackValue::Number { negate, exp } = value {
+                if let LookbackValue::Number {
+                    negate,
+                    exp,
+                    integer,
+                } = value
+                {
                     self.state = DeserializerState::Collection {
                         at_start: false,
                         streaming_depth,
@@ -290,6 +373,9 @@ impl<Reader: TextReader> TextDeserializer<Reader> {
                             ));
                         }
                     }
+                    if !integer {
+                        builder.mark_non_integer();
+                    }
                     let exp = i32::from(exp);
                     T::from_builder(builder, negate, exp)
                         .ok_or_else(|| DeserializeError::new(pos.clone(), NumberOverflow))
@@ -307,6 +393,13 @@ impl<Reader: TextReader> TextDeserializer<Reader> {
         }
     }
 
+    /// Wraps this deserializer in an [`Erase`], allowing it to be used as a
+    /// `dyn ErasedDeserializer`. Since positions may borrow from the input, errors are converted
+    /// to `DeserializeError<String>` using the [`std::fmt::Display`] form of their position.
+    pub fn erased(&mut self) -> Erase<'_, Self> {
+        Erase::with_map_error(self, |err| ErasedError::new(err.map_position(|pos| pos.to_string())))
+    }
+
     /// Verifies that there is no more data to read and closes the [`TextDeserializer`].
     pub fn close(mut self) -> Result<(), DeserializeError<Reader::Position>> {
         assert!(matches!(
@@ -322,6 +415,35 @@ impl<Reader: TextReader> TextDeserializer<Reader> {
         Ok(())
     }
 
+    /// Checks whether the value at the top of the stack is the key for an object being read as a
+    /// map. Since object keys are always strings, this returns an error which says that the
+    /// value should have one of the given types.
+    fn check_map_key(
+        &self,
+        expected: &'static [ValueType],
+    ) -> Result<(), DeserializeError<Reader::Position>> {
+        if self.at_map_key {
+            Err(DeserializeError::new(
+                self.error_pos.clone(),
+                DeserializeErrorMessage::Expected(expected),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Gets the position of the value at the top of the stack, for use in errors that are
+    /// detected after the value has been read.
+    fn value_pos(&self) -> Reader::Position {
+        match self.state {
+            DeserializerState::StreamingValue => self.reader.position(),
+            DeserializerState::LookbackValue { index, .. } => {
+                self.outline.lookback_items[index].pos.clone()
+            }
+            _ => self.error_pos.clone(),
+        }
+    }
+
     /// Constructs an error in response to an attempt to read a virtual `null` as anything other
     /// than a `null` literal.
     fn error_unexpected_virtual_null(
@@ -337,6 +459,104 @@ impl<Reader: TextReader> TextDeserializer<Reader> {
             todo!()
         }
     }
+
+    /// Checks that the value at the top of the stack has one of the given types. Unlike the
+    /// checks made while reading the value, this doesn't consume any of the value when it fails,
+    /// so the value can still be skipped afterwards (e.g. by [`Deserializer::unwind`]).
+    fn check_value_type(
+        &self,
+        expected: &'static [ValueType],
+    ) -> Result<(), DeserializeError<Reader::Position>> {
+        let has_value = match self.state {
+            // The end of the input is reported when the value is read
+            DeserializerState::StreamingValue => self.reader.peek().is_some(),
+            DeserializerState::LookbackValue { .. } => true,
+            _ => false,
+        };
+        if has_value && !expected.contains(&self.peek_value_type()) {
+            return Err(self.error_expected_type(expected));
+        }
+        Ok(())
+    }
+
+    /// Gets the number of opened collections on the stack, as seen through the [`Outliner`]
+    /// interface. This excludes the arrays for the entries of maps that are represented as
+    /// arrays of pairs.
+    fn depth(&self) -> usize {
+        let num_pairs = self
+            .maps
+            .iter()
+            .filter(|map| matches!(map, MapFrame::Array { pair_open: true, .. }))
+            .count();
+        self.outline.stack_items.len() - num_pairs
+    }
+
+    /// Implements [`Deserializer::unwind`]. Returns `false` if the collection at the given depth
+    /// has already been popped.
+    fn try_unwind(&mut self, target: usize) -> Result<bool, DeserializeError<Reader::Position>> {
+        self.at_map_key = false;
+        loop {
+            match self.state {
+                DeserializerState::StreamingValue
+                | DeserializerState::LookbackValue { .. }
+                | DeserializerState::NullValue { .. } => self.skip_value()?,
+                DeserializerState::StreamingString { .. }
+                | DeserializerState::LookbackString { .. } => self.skip_str()?,
+                DeserializerState::Collection { .. } => {
+                    let depth = self.depth();
+                    if depth <= target {
+                        return Ok(depth == target);
+                    }
+                    let stack_len = self.outline.stack_items.len();
+                    match self.maps.last_mut() {
+                        Some(MapFrame::Array { depth, pair_open })
+                            if *pair_open && *depth + 1 == stack_len =>
+                        {
+                            // Skip the rest of the pair for the current entry
+                            *pair_open = false;
+                            while self.next_item()? {
+                                self.skip_value()?;
+                            }
+                            continue;
+                        }
+                        Some(MapFrame::Object { depth } | MapFrame::Array { depth, .. })
+                            if *depth == stack_len =>
+                        {
+                            self.maps.pop();
+                        }
+                        _ => (),
+                    }
+                    match self.peek_collection_type() {
+                        CollectionType::Object => self.skip_object()?,
+                        CollectionType::Array => {
+                            while self.next_item()? {
+                                self.skip_value()?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// If the value on top of the stack is being streamed, reads it into `lookback_items`, so
+    /// that it can be inspected before being read, or read more than once.
+    fn buffer_value(&mut self) -> Result<(), DeserializeError<Reader::Position>> {
+        if let DeserializerState::StreamingValue = self.state {
+            let data_index = self.outline.lookback_data.len();
+            let index = self.reader.read_lookback_value(
+                &self.config,
+                data_index,
+                0,
+                &mut self.outline,
+            )?;
+            self.state = DeserializerState::LookbackValue {
+                index,
+                streaming_depth: self.outline.top_depth(),
+            };
+        }
+        Ok(())
+    }
 }
 
 impl<Reader: TextReader> Outliner for TextDeserializer<Reader> {
@@ -346,7 +566,13 @@ impl<Reader: TextReader> Outliner for TextDeserializer<Reader> {
         true
     }
 
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
     fn pop_null(&mut self) -> Result<(), Self::Error> {
+        self.check_map_key(&[ValueType::Null])?;
+        self.check_value_type(&[ValueType::Null])?;
         match self.state {
             DeserializerState::StreamingValue => {
                 self.state = DeserializerState::Collection {
@@ -408,6 +634,11 @@ impl<Reader: TextReader> Outliner for TextDeserializer<Reader> {
     }
 
     fn open_str(&mut self) -> Result<(), Self::Error> {
+        if std::mem::take(&mut self.at_map_key) {
+            // The key string for the entry has already been opened by `next_key`
+            return Ok(());
+        }
+        self.check_value_type(&[ValueType::String])?;
         match self.state {
             DeserializerState::StreamingValue => {
                 let pos = self.reader.position();
@@ -435,6 +666,7 @@ impl<Reader: TextReader> Outliner for TextDeserializer<Reader> {
                         head_index: data.start as usize - base_ptr as usize,
                         end_index: data.end as usize - base_ptr as usize,
                         value_index: None,
+                        source_index: Some(index),
                         streaming_depth,
                     };
                     Ok(())
@@ -456,6 +688,23 @@ impl<Reader: TextReader> Outliner for TextDeserializer<Reader> {
         todo!()
     }
 
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        let pos = self.value_pos();
+        self.open_str()?;
+        self.error_pos = pos;
+        self.bytes = BytesDecoder::new(self.config.bytes_encoding);
+        self.bytes_at_end = false;
+        Ok(())
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        if self.next_bytes_chunk()?.is_some() {
+            while self.next_bytes_chunk()?.is_some() {}
+            return Err(DeserializeError::new(self.error_pos.clone(), ExcessBytes));
+        }
+        Ok(())
+    }
+
     fn open_struct(&mut self, type_name: Option<&'static str>) -> Result<(), Self::Error> {
         super::open_struct(self, type_name)
     }
@@ -489,10 +738,48 @@ impl<Reader: TextReader> Outliner for TextDeserializer<Reader> {
     fn close_list(&mut self) -> Result<(), Self::Error> {
         super::close_list(self)
     }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        if !self.next_key()? {
+            return Err(self.error_missing_item());
+        }
+        Ok(())
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        match self.maps.last().expect(NOT_MAP) {
+            MapFrame::Object { .. } => {
+                assert!(
+                    matches!(
+                        self.state,
+                        DeserializerState::StreamingValue | DeserializerState::LookbackValue { .. }
+                    ),
+                    "{}",
+                    NOT_VALUE
+                );
+                Ok(())
+            }
+            MapFrame::Array { .. } => self.push_item(),
+        }
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        match self.maps.pop().expect(NOT_MAP) {
+            MapFrame::Object { .. } => self.close_object(),
+            MapFrame::Array { pair_open, .. } => {
+                if pair_open {
+                    self.close_list()?;
+                }
+                self.close_list()
+            }
+        }
+    }
 }
 
 impl<Reader: TextReader> JsonOutliner for TextDeserializer<Reader> {
     fn open_object(&mut self) -> Result<(), Self::Error> {
+        self.check_map_key(&[ValueType::Object])?;
+        self.check_value_type(&[ValueType::Object])?;
         match self.state {
             DeserializerState::StreamingValue => {
                 let pos = self.reader.position();
@@ -601,6 +888,8 @@ impl<Reader: TextReader> JsonOutliner for TextDeserializer<Reader> {
 
 impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
     fn get_bool(&mut self) -> Result<bool, Self::Error> {
+        self.check_map_key(&[ValueType::Bool])?;
+        self.check_value_type(&[ValueType::Bool])?;
         match self.state {
             DeserializerState::StreamingValue => {
                 self.state = DeserializerState::Collection {
@@ -653,6 +942,10 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
         self.read_number()
     }
 
+    fn get_i128(&mut self) -> Result<i128, Self::Error> {
+        self.read_number()
+    }
+
     fn get_u8(&mut self) -> Result<u8, Self::Error> {
         self.read_number()
     }
@@ -669,6 +962,10 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
         self.read_number()
     }
 
+    fn get_u128(&mut self) -> Result<u128, Self::Error> {
+        self.read_number()
+    }
+
     fn get_f32(&mut self) -> Result<f32, Self::Error> {
         self.read_number()
     }
@@ -678,7 +975,15 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
     }
 
     fn get_char(&mut self) -> Result<char, Self::Error> {
-        todo!()
+        let pos = self.value_pos();
+        self.open_str()?;
+        if let Some(ch) = self.next_char()? {
+            if self.next_char()?.is_none() {
+                return Ok(ch);
+            }
+            self.skip_str()?;
+        }
+        Err(DeserializeError::new(pos, InvalidChar))
     }
 
     fn next_char(&mut self) -> Result<Option<char>, Self::Error> {
@@ -707,6 +1012,7 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
                 end_index,
                 value_index,
                 streaming_depth,
+                ..
             } => {
                 let end_index = *end_index;
                 Ok(if *head_index < end_index {
@@ -739,6 +1045,30 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
         }
     }
 
+    fn next_bytes_chunk(&mut self) -> Result<Option<&[u8]>, Self::Error> {
+        self.bytes_buf.clear();
+        if std::mem::take(&mut self.bytes_at_end) {
+            return Ok(None);
+        }
+        while self.bytes_buf.len() < BYTES_CHUNK_LEN {
+            if let Some(ch) = self.next_char()? {
+                if !self.bytes.push(ch, &mut self.bytes_buf) {
+                    return Err(DeserializeError::new(self.error_pos.clone(), InvalidBytes));
+                }
+            } else {
+                if !self.bytes.finish(&mut self.bytes_buf) {
+                    return Err(DeserializeError::new(self.error_pos.clone(), InvalidBytes));
+                }
+                if self.bytes_buf.is_empty() {
+                    return Ok(None);
+                }
+                self.bytes_at_end = true;
+                break;
+            }
+        }
+        Ok(Some(&self.bytes_buf))
+    }
+
     fn get_tag(
         &mut self,
         max_index: usize,
@@ -757,6 +1087,8 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
     }
 
     fn open_list(&mut self) -> Result<Option<usize>, Self::Error> {
+        self.check_map_key(&[ValueType::Array])?;
+        self.check_value_type(&[ValueType::Array])?;
         match self.state {
             DeserializerState::StreamingValue => {
                 let pos = self.reader.position();
@@ -857,6 +1189,159 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
         }
     }
 
+    fn open_map(&mut self) -> Result<Option<usize>, Self::Error> {
+        match self.peek_value_type() {
+            ValueType::Object => {
+                self.open_object()?;
+                let depth = self.outline.stack_items.len();
+                self.maps.push(MapFrame::Object { depth });
+            }
+            ValueType::Array => {
+                self.open_list()?;
+                let depth = self.outline.stack_items.len();
+                self.maps.push(MapFrame::Array {
+                    depth,
+                    pair_open: false,
+                });
+            }
+            _ => return Err(self.error_expected_type(&[ValueType::Object, ValueType::Array])),
+        }
+        Ok(None)
+    }
+
+    fn next_key(&mut self) -> Result<bool, Self::Error> {
+        match self.maps.last_mut().expect(NOT_MAP) {
+            MapFrame::Object { .. } => {
+                if self.next_entry()? {
+                    self.error_pos = match self.state {
+                        DeserializerState::LookbackString {
+                            value_index: Some(index),
+                            ..
+                        } => self.outline.lookback_items[index].pos.clone(),
+                        _ => self.reader.position(),
+                    };
+                    self.at_map_key = true;
+                    Ok(true)
+                } else {
+                    self.maps.pop();
+                    Ok(false)
+                }
+            }
+            MapFrame::Array { pair_open, .. } => {
+                if std::mem::take(pair_open) {
+                    self.close_list()?;
+                }
+                if !self.next_item()? {
+                    self.maps.pop();
+                    return Ok(false);
+                }
+                self.open_list()?;
+                if let Some(MapFrame::Array { pair_open, .. }) = self.maps.last_mut() {
+                    *pair_open = true;
+                }
+                self.push_item()?;
+                Ok(true)
+            }
+        }
+    }
+
+    fn peek_kind(&mut self) -> Result<ValueKind, Self::Error> {
+        Ok(match self.peek_value_type() {
+            ValueType::Null => {
+                if let DeserializerState::StreamingValue = self.state {
+                    let pos = self.reader.position();
+                    match self.reader.peek() {
+                        Some('n') => (),
+                        Some(_) => return Err(DeserializeError::new(pos, UnexpectedChar)),
+                        None => return Err(DeserializeError::new(pos, UnexpectedEof)),
+                    }
+                }
+                ValueKind::Null
+            }
+            ValueType::Bool => ValueKind::Bool,
+            ValueType::Number => {
+                // The digits of a streaming number need to be read to tell whether it is an
+                // integer
+                self.buffer_value()?;
+                let DeserializerState::LookbackValue { index, .. } = self.state else {
+                    unreachable!()
+                };
+                match self.outline.lookback_items[index].value {
+                    Some(LookbackValue::Number { integer: true, .. }) => ValueKind::Integer,
+                    _ => ValueKind::Float,
+                }
+            }
+            ValueType::String => ValueKind::Str,
+            ValueType::Object => ValueKind::Struct,
+            ValueType::Array => ValueKind::List,
+        })
+    }
+
+    fn unwind(&mut self, depth: usize) -> bool {
+        matches!(self.try_unwind(depth), Ok(true))
+    }
+
+    fn checkpoint(&mut self) -> Result<bool, Self::Error> {
+        if self.at_map_key {
+            // The key string for the entry has already been opened by `next_key`
+            return Ok(false);
+        }
+        self.buffer_value()?;
+        let values = match self.state {
+            DeserializerState::LookbackValue { index, .. } => {
+                let end = self.outline.subtree_end(index);
+                let items = &self.outline.lookback_items[index..end];
+                items.iter().map(|item| item.value).collect()
+            }
+            DeserializerState::NullValue { .. } => Vec::new(),
+            _ => panic!("{}", NOT_VALUE),
+        };
+        self.checkpoints.push(Checkpoint {
+            state: self.state,
+            error_pos: self.error_pos.clone(),
+            depth: self.outline.stack_items.len(),
+            num_maps: self.maps.len(),
+            values,
+        });
+        Ok(true)
+    }
+
+    fn rewind(&mut self) {
+        let checkpoint = self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+
+        // Remove the lookback keys for the objects opened since the checkpoint
+        for (i, stack_item) in self.outline.stack_items.iter().enumerate().skip(checkpoint.depth) {
+            if let CollectionType::Object = stack_item.collection_type {
+                let depth = NonZeroU32::new(u32::try_from(i + 1).unwrap()).unwrap();
+                let mut child_index = stack_item.first_child_index;
+                while let Some(item) = self.outline.lookback_items.get(child_index) {
+                    let hash = key_hash(depth, item.key_bytes(&self.outline.lookback_data));
+                    self.outline.lookback_keys.remove_entry(hash, |key| {
+                        key.depth == depth && key.index == child_index
+                    });
+                    child_index = item.next_sibling_index;
+                }
+            }
+        }
+        self.outline.stack_items.truncate(checkpoint.depth);
+        self.maps.truncate(checkpoint.num_maps);
+
+        // Restore the values that have been read since the checkpoint
+        if let DeserializerState::LookbackValue { index, .. } = checkpoint.state {
+            let items = &mut self.outline.lookback_items[index..];
+            for (item, value) in items.iter_mut().zip(checkpoint.values) {
+                item.value = value;
+            }
+        }
+        self.state = checkpoint.state;
+        self.error_pos = checkpoint.error_pos;
+        self.at_map_key = false;
+    }
+
+    fn release(&mut self) {
+        self.checkpoints.pop().expect(NO_CHECKPOINT_ERROR);
+    }
+
     fn error(&self, source: Box<dyn std::error::Error + Send + Sync>) -> Self::Error {
         DeserializeError::new(
             self.error_pos.clone(),
@@ -878,8 +1363,21 @@ impl<Reader: TextReader> Deserializer for TextDeserializer<Reader> {
     }
 }
 
+impl<Reader: TextReader> ShapeDeserializer for TextDeserializer<Reader> {
+    fn get_number(&mut self) -> Result<Number, Self::Error> {
+        self.read_number()
+    }
+
+    fn has_str_keys(&self) -> bool {
+        matches!(self.maps.last().expect(NOT_MAP), MapFrame::Object { .. })
+    }
+}
+
 impl<Reader: TextReader> JsonDeserializer for TextDeserializer<Reader> {
     fn peek_value_type(&self) -> ValueType {
+        if self.at_map_key {
+            return ValueType::String;
+        }
         match self.state {
             DeserializerState::StreamingValue => match self.reader.peek() {
                 Some('"') => ValueType::String,
@@ -1088,6 +1586,7 @@ impl<Reader: TextReader> JsonDeserializer for TextDeserializer<Reader> {
                 head_index: key_data.start as usize - base_ptr as usize,
                 end_index: key_data.end as usize - base_ptr as usize,
                 value_index: Some(item_index),
+                source_index: None,
                 streaming_depth: *streaming_depth,
             };
             return Ok(true);
@@ -1117,6 +1616,9 @@ impl<Reader: TextReader> JsonDeserializer for TextDeserializer<Reader> {
     }
 
     fn error_expected_type(&self, expected: &'static [ValueType]) -> Self::Error {
+        if let Err(err) = self.check_map_key(expected) {
+            return err;
+        }
         DeserializeError::new(
             match self.state {
                 DeserializerState::StreamingValue => self.reader.position(),
@@ -1146,6 +1648,53 @@ impl<Reader: TextReader> JsonDeserializer for TextDeserializer<Reader> {
     }
 }
 
+impl<'de> BorrowDeserializer<'de> for TextDeserializer<&'de str> {
+    fn flush_borrowed_str(&mut self) -> Result<Cow<'de, str>, Self::Error> {
+        let borrowed = match &mut self.state {
+            DeserializerState::StreamingString { .. } => {
+                let rem: &'de str = self.reader;
+                unescaped_len(rem).map(|len| {
+                    self.reader = &rem[len..];
+                    &rem[..len]
+                })
+            }
+            DeserializerState::LookbackString {
+                head_index,
+                end_index,
+                source_index: Some(index),
+                ..
+            } => {
+                // If the original string has no escape sequences, it is identical to the
+                // string data in `lookback_data`, so the unread suffix can be found in the input
+                let source = self.outline.lookback_items[*index].pos.as_str();
+                let str = source
+                    .strip_prefix('"')
+                    .and_then(|str| unescaped_len(str).map(|len| &str[..len]));
+                str.map(|str| {
+                    let rem_len = *end_index - std::mem::replace(head_index, *end_index);
+                    &str[(str.len() - rem_len)..]
+                })
+            }
+            _ => None,
+        };
+        if let Some(str) = borrowed {
+            // Read the closing quote to pop the string
+            let end = self.next_char()?;
+            debug_assert!(end.is_none());
+            Ok(Cow::Borrowed(str))
+        } else {
+            Ok(Cow::Owned(self.flush_str()?.into_owned()))
+        }
+    }
+}
+
+/// Gets the length (in bytes) of the remainder of a quoted string, up to the closing quote, if
+/// it does not contain any escape sequences.
+fn unescaped_len(str: &str) -> Option<usize> {
+    let end = str.find(['"', '\\'])?;
+    (str.as_bytes()[end] == b'"').then_some(end)
+}
+
 impl<Position> StackItem<Position> {
     /// Asserts that this stack item is for an object.
     pub fn assert_object(&self) {
@@ -1201,6 +1750,30 @@ impl<Position> Outline<Position> {
         (pos, value, data)
     }
 
+    /// Gets the index in `lookback_items` immediately following the [`LookbackItem`] at the
+    /// given index and all of its descendants, assuming that none of them have been read.
+    pub fn subtree_end(&self, mut index: usize) -> usize {
+        loop {
+            let item = &self.lookback_items[index];
+            if item.next_sibling_index < usize::MAX {
+                return item.next_sibling_index;
+            }
+            match item.value {
+                Some(
+                    LookbackValue::Object { has_entries: true }
+                    | LookbackValue::Array { has_items: true },
+                ) => {
+                    // Continue from the last child of the item
+                    index += 1;
+                    while self.lookback_items[index].next_sibling_index < usize::MAX {
+                        index = self.lookback_items[index].next_sibling_index;
+                    }
+                }
+                _ => return index + 1,
+            }
+        }
+    }
+
     /// Appends a [`LookbackItem`] to `lookback_items`. `next_sibling_index` will be set to
     /// the current value of `last_child_index` and `last_child_index` will be updated to the
     /// index of the new item.
@@ -1568,6 +2141,7 @@ trait TextReaderExt: TextReader {
             }
 
             // Parse fractional component (we already read the decimal point)
+            builder.mark_non_integer();
             match self.next() {
                 Some(ch @ '0'..='9') => {
                     decimal_exp -= 1;
@@ -1599,6 +2173,7 @@ trait TextReaderExt: TextReader {
         }
 
         // Parse exponent (we already read the 'e'/'E').
+        builder.mark_non_integer();
         let mut exp_builder: u32 = 0;
         let mut negate_exp = false;
         match self.next() {
@@ -1732,21 +2307,6 @@ trait TextReaderExt: TextReader {
         Ok(())
     }
 
-    /// Reads a quoted string into `data`, starting from the first character inside the quotes, and
-    /// ending after the end quote.
-    fn read_str_into(&mut self, str: &mut String) -> Result<(), DeserializeError<Self::Position>> {
-        loop {
-            let ch = match self.next() {
-                Some('"') => break,
-                Some('\\') => self.read_escape_sequence()?,
-                Some(ch) => ch,
-                None => return Err(DeserializeError::new(self.position(), UnexpectedEof)),
-            };
-            str.push(ch);
-        }
-        Ok(())
-    }
-
     /// Advances the stream until just past a colon (`:`), skipping whitespace.
     fn skip_past_colon(
         &mut self,
@@ -1777,7 +2337,7 @@ trait TextReaderExt: TextReader {
         outline: &mut Outline<Self::Position>,
     ) -> Result<usize, DeserializeError<Self::Position>> {
         let pos = self.position();
-        let mut depth = outline.top_depth().unwrap();
+        let mut depth = outline.stack_items.len();
         let mut last_child_index = usize::MAX;
         let mut collection_type = CollectionType::Object; // Default value prior to being set.
         let end_depth = depth;
@@ -1804,12 +2364,14 @@ trait TextReaderExt: TextReader {
                 }
                 Some('-' | '0'..='9') => {
                     let pos = self.position();
-                    let (negate, exp) = {
+                    let (negate, exp, integer) = {
                         let mut builder = LookbackNumBuilder {
                             target: &mut outline.lookback_data,
                             buf: None,
+                            integer: true,
                         };
-                        self.read_number_into_builder(&mut builder)?
+                        let (negate, exp) = self.read_number_into_builder(&mut builder)?;
+                        (negate, exp, builder.integer)
                     };
                     let Ok(exp) = exp.try_into() else {
                         return Err(DeserializeError::new(
@@ -1822,7 +2384,11 @@ trait TextReaderExt: TextReader {
                         pos,
                         data_index,
                         key_len_active,
-                        LookbackValue::Number { negate, exp },
+                        LookbackValue::Number {
+                            negate,
+                            exp,
+                            integer,
+                        },
                     );
                 }
                 Some('{') => {
@@ -2004,7 +2570,7 @@ trait TextReaderExt: TextReader {
                 let first_child_index =
                     correct_items(&mut outline.lookback_items, last_child_index);
                 let parent_index = first_child_index - 1;
-                depth = NonZeroU32::new(u32::from(depth) - 1).unwrap();
+                depth -= 1;
                 last_child_index = parent_index;
                 collection_type = if outline.lookback_items[parent_index].key_len_active & 1 > 0 {
                     CollectionType::Object
@@ -2049,6 +2615,7 @@ impl<T: TextReader> TextReaderExt for T {}
 struct LookbackNumBuilder<'a> {
     target: &'a mut Vec<u8>,
     buf: Option<u8>,
+    integer: bool,
 }
 
 impl NumBuilder for LookbackNumBuilder<'_> {
@@ -2061,6 +2628,10 @@ impl NumBuilder for LookbackNumBuilder<'_> {
         }
         true
     }
+
+    fn mark_non_integer(&mut self) {
+        self.integer = false;
+    }
 }
 
 impl Drop for LookbackNumBuilder<'_> {
@@ -2102,6 +2673,9 @@ pub enum DeserializeErrorMessage {
     KeyTooLong,
     MissingItems,
     ExcessItems,
+    InvalidBytes,
+    ExcessBytes,
+    InvalidChar,
 }
 
 impl<Position> DeserializeError<Position> {
@@ -2119,6 +2693,12 @@ impl<Position> DeserializeError<Position> {
     pub fn message(&self) -> &DeserializeErrorMessage {
         &self.0.message
     }
+
+    /// Converts the position of this error using the given function.
+    pub fn map_position<N>(self, f: impl FnOnce(Position) -> N) -> DeserializeError<N> {
+        let DeserializeErrorInner { pos, message } = *self.0;
+        DeserializeError::new(f(pos), message)
+    }
 }
 
 impl std::fmt::Display for DeserializeErrorMessage {
@@ -2157,6 +2737,9 @@ impl std::fmt::Display for DeserializeErrorMessage {
             KeyTooLong => f.write_str("object key too long"),
             MissingItems => f.write_str("array has fewer items than expected"),
             ExcessItems => f.write_str("array has more items than expected"),
+            InvalidBytes => f.write_str("invalid byte string encoding"),
+            ExcessBytes => f.write_str("byte string is longer than expected"),
+            InvalidChar => f.write_str("expected string with exactly one character"),
         }
     }
 }
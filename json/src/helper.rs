@@ -102,7 +102,7 @@ impl<'a, O: JsonOutliner + ?Sized> Object<'a, O> {
     }
 
     /// Asserts that the object has an entry with the specified key, returning its value.
-    pub fn entry(&mut self, key: &str) -> Result<Value<O>, O::Error> {
+    pub fn entry(&mut self, key: &str) -> Result<Value<'_, O>, O::Error> {
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
         self.source.push_entry(key)?;
@@ -121,7 +121,7 @@ impl<'a, O: JsonOutliner + ?Sized> Object<'a, O> {
 impl<'a, D: JsonDeserializer + ?Sized> Object<'a, D> {
     /// Attempts to get the value for a particular entry in the object, returning `Ok(None)` if
     /// there isn't an entry with the specified key.
-    pub fn try_entry(&mut self, key: &str) -> Result<Option<Value<D>>, D::Error> {
+    pub fn try_entry(&mut self, key: &str) -> Result<Option<Value<'_, D>>, D::Error> {
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
         if self.source.try_push_entry(key)? {
@@ -135,7 +135,7 @@ impl<'a, D: JsonDeserializer + ?Sized> Object<'a, D> {
     /// Attempts to get the next unread entry for the object, returning `Ok(None)` if there are
     /// no entries left. In that case, the object will be automatically closed and should not
     /// be used again.
-    pub fn next_entry(&mut self) -> Result<Option<Entry<D>>, D::Error> {
+    pub fn next_entry(&mut self) -> Result<Option<Entry<'_, D>>, D::Error> {
         assert!(self.ready_flag, "{}", INVALID_STATE_ERROR);
         self.ready_flag = false;
         if self.source.next_entry()? {
@@ -189,7 +189,7 @@ impl<'a, D: JsonDeserializer + ?Sized> Entry<'a, D> {
     }
 
     /// Gets the key string for this [`Entry`]. The key may only be retrieved from the entry once.
-    pub fn key(&mut self) -> Result<Cow<str>, D::Error> {
+    pub fn key(&mut self) -> Result<Cow<'_, str>, D::Error> {
         assert!(!self.key_read_flag, "{}", INVALID_STATE_ERROR);
         self.key_read_flag = true;
         self.source.flush_str()
@@ -219,7 +219,7 @@ impl<'a, D: JsonDeserializer + ?Sized> Collection<'a, D> {
     /// and list items for lists. Returns `Ok(None)` if there are no entries remaining. In that
     /// case, the collection will be automatically closed and should not be used again.
     #[allow(clippy::should_implement_trait)]
-    pub fn next(&mut self) -> Result<Option<Value<D>>, D::Error> {
+    pub fn next(&mut self) -> Result<Option<Value<'_, D>>, D::Error> {
         match self {
             Collection::Object(obj) => obj.next_entry()?.map(|entry| entry.value()).transpose(),
             Collection::List(list) => list.next(),
@@ -1,7 +1,9 @@
 pub mod deserialize;
 pub mod serialize;
+mod bytes;
 mod helper;
 mod outliner;
+pub use bytes::BytesEncoding;
 pub use deserialize::*;
 pub use serialize::*;
 pub use helper::*;
@@ -24,3 +26,28 @@ pub enum CollectionType {
     Object,
     Array,
 }
+
+/// An error that can occur during [`reformat`].
+pub type ReformatError<Position, WriterError> =
+    serdere::TranscodeError<DeserializeError<Position>, SerializeError<WriterError>>;
+
+/// Reads a JSON value from a [`serdere::TextReader`] and writes it to a [`serdere::TextWriter`]
+/// using the given [`TextSerializerConfig`], e.g. to convert between compact and pretty-printed
+/// JSON. The value is streamed using [`serdere::transcode`], so it doesn't need to be fully
+/// loaded into memory.
+pub fn reformat<Reader: serdere::TextReader, Writer: serdere::TextWriter>(
+    reader: Reader,
+    writer: Writer,
+    config: TextSerializerConfig,
+) -> Result<(), ReformatError<Reader::Position, Writer::Error>> {
+    use serdere::{TranscodeError, Value};
+    let mut d = TextDeserializer::new(TextDeserializerConfig::default(), reader)
+        .map_err(TranscodeError::Deserialize)?;
+    let mut s = TextSerializer::new(config, writer);
+    let (mut d_done_flag, mut s_done_flag) = (false, false);
+    serdere::transcode(
+        Value::new(&mut d, &mut d_done_flag),
+        Value::new(&mut s, &mut s_done_flag),
+    )?;
+    d.close().map_err(TranscodeError::Deserialize)
+}
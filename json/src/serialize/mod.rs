@@ -2,6 +2,7 @@ mod text;
 
 use super::JsonOutliner;
 use serdere::{Serialize, Serializer, TextWriter, Value};
+use std::convert::Infallible;
 pub use text::*;
 
 /// Extends [`Serializer`] with JSON-specific functionality.
@@ -34,7 +35,7 @@ pub fn put_tag<S: JsonSerializer + ?Sized>(
 pub fn to_writer<Writer: TextWriter, T: Serialize<TextSerializer<Writer>> + ?Sized>(
     writer: Writer,
     value: &T,
-) -> Result<(), Writer::Error> {
+) -> Result<(), SerializeError<Writer::Error>> {
     to_writer_using(writer, value, &mut ())
 }
 
@@ -47,7 +48,7 @@ pub fn to_writer_using<
     writer: Writer,
     value: &T,
     context: &mut Ctx,
-) -> Result<(), Writer::Error> {
+) -> Result<(), SerializeError<Writer::Error>> {
     let mut s = TextSerializer::new(TextSerializerConfig::default(), writer);
     let mut done_flag = false;
     value.serialize(Value::new(&mut s, &mut done_flag), context)?;
@@ -55,7 +56,9 @@ pub fn to_writer_using<
 }
 
 /// Serializes a value of type `T` as a JSON string.
-pub fn to_str<T: for<'a> Serialize<TextSerializer<&'a mut String>> + ?Sized>(value: &T) -> String {
+pub fn to_str<T: for<'a> Serialize<TextSerializer<&'a mut String>> + ?Sized>(
+    value: &T,
+) -> Result<String, SerializeError<Infallible>> {
     to_str_using(value, &mut ())
 }
 
@@ -66,8 +69,8 @@ pub fn to_str_using<
 >(
     value: &T,
     context: &mut Ctx,
-) -> String {
+) -> Result<String, SerializeError<Infallible>> {
     let mut str = String::new();
-    to_writer_using(&mut str, value, context).unwrap();
-    str
+    to_writer_using(&mut str, value, context)?;
+    Ok(str)
 }
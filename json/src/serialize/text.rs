@@ -1,5 +1,6 @@
-use crate::{JsonSerializer, JsonOutliner};
-use serdere::{Outliner, Serializer, TextWriter};
+use crate::bytes::BytesEncoder;
+use crate::{BytesEncoding, JsonSerializer, JsonOutliner};
+use serdere::{Erase, Outliner, Serializer, TextWriter, INVALID_STATE_ERROR};
 
 /// A [`JsonSerializer`] which writes to a [`TextWriter`].
 pub struct TextSerializer<Writer: TextWriter> {
@@ -8,20 +9,48 @@ pub struct TextSerializer<Writer: TextWriter> {
     depth: u32,
     in_key: bool,
     at_first: bool,
+    bytes: BytesEncoder,
+    maps: Vec<MapState>,
+    at_key: bool,
 }
 
+/// Describes how an opened map is being written by a [`TextSerializer`]. This is determined by
+/// the `str_keys` flag given when the map is opened: maps with string keys are written as
+/// objects, and all other maps are written as arrays of `[key, value]` pairs.
+#[derive(Debug, Clone, Copy)]
+enum MapState {
+    /// The map is being written as an object.
+    Object,
+
+    /// The map is being written as an array of pairs.
+    Array {
+        /// Indicates whether the pair for the current entry has been opened.
+        pair_open: bool,
+    },
+}
+
+/// The error message for a panic that occurs when a map operation is used while the top of the
+/// serialization stack is not an opened map.
+const NOT_MAP_ERROR: &str = "top of the serialization stack is not an opened map";
+
 /// Encapsulates the configuration options for a [`TextSerializer`].
 #[derive(Debug, Clone, Copy)]
 pub struct TextSerializerConfig {
     /// The character sequence used for one indentation level (e.g. "\t" or "    "). If [`None`],
     /// the written JSON will be compact, without any line breaks or indentation.
     pub indent: Option<&'static str>,
+
+    /// The encoding used to write byte strings.
+    pub bytes_encoding: BytesEncoding,
 }
 
 #[allow(clippy::derivable_impls)]
 impl Default for TextSerializerConfig {
     fn default() -> Self {
-        Self { indent: None }
+        Self {
+            indent: None,
+            bytes_encoding: BytesEncoding::Base64,
+        }
     }
 }
 
@@ -35,28 +64,91 @@ impl<Writer: TextWriter> TextSerializer<Writer> {
             depth: 0,
             in_key: false,
             at_first: false,
+            bytes: BytesEncoder::new(config.bytes_encoding),
+            maps: Vec::new(),
+            at_key: false,
         }
     }
 
+    /// Wraps this serializer in an [`Erase`], allowing it to be used as a
+    /// `dyn ErasedSerializer`.
+    pub fn erased(&mut self) -> Erase<'_, Self>
+    where
+        Writer::Error: Send + Sync + 'static,
+    {
+        Erase::new(self)
+    }
+
     /// Closes the serializer and returns the underlying [`TextWriter`].
     pub fn close(self) -> Writer {
         self.writer
     }
+
+    /// Prepares to write a value. If the value is the key for a map entry, this will write the
+    /// punctuation preceding the entry (and the start of the map, if this is the first entry).
+    /// `is_str` indicates whether the value will be written as a JSON string.
+    fn begin_value(&mut self, is_str: bool) -> Result<(), SerializeError<Writer::Error>> {
+        if std::mem::take(&mut self.at_key) {
+            match self.maps.last_mut().unwrap() {
+                MapState::Object => {
+                    if !is_str {
+                        return Err(SerializeError::NonStringKey);
+                    }
+                    self.begin_entry()?;
+                    self.in_key = true;
+                }
+                MapState::Array { pair_open } => {
+                    if std::mem::replace(pair_open, true) {
+                        // Close the pair for the previous entry
+                        self.close_list()?;
+                    }
+                    self.push_item()?;
+                    self.open_list_streaming()?;
+                    self.push_item()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the separator and indentation that precedes an object entry.
+    fn begin_entry(&mut self) -> Result<(), Writer::Error> {
+        if self.at_first {
+            self.at_first = false;
+        } else {
+            self.writer.write_char(',')?;
+        }
+        if let Some(indent) = self.config.indent {
+            self.writer.write_char('\n')?;
+            for _ in 0..self.depth {
+                self.writer.write_str(indent)?;
+            }
+        } else {
+            self.writer.write_char(' ')?;
+        }
+        Ok(())
+    }
 }
 
 impl<Writer: TextWriter> Outliner for TextSerializer<Writer> {
-    type Error = Writer::Error;
+    type Error = SerializeError<Writer::Error>;
 
     fn supports_null(&self) -> bool {
         true
     }
 
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
     fn pop_null(&mut self) -> Result<(), Self::Error> {
-        self.writer.write_str("null")
+        self.begin_value(false)?;
+        self.writer.write_str("null").map_err(SerializeError::Writer)
     }
 
     fn open_str(&mut self) -> Result<(), Self::Error> {
-        self.writer.write_char('\"')
+        self.begin_value(true)?;
+        self.writer.write_char('\"').map_err(SerializeError::Writer)
     }
 
     fn close_str(&mut self) -> Result<(), Self::Error> {
@@ -68,6 +160,15 @@ impl<Writer: TextWriter> Outliner for TextSerializer<Writer> {
         Ok(())
     }
 
+    fn open_bytes(&mut self) -> Result<(), Self::Error> {
+        self.open_str()
+    }
+
+    fn close_bytes(&mut self) -> Result<(), Self::Error> {
+        self.bytes.finish(&mut self.writer)?;
+        self.close_str()
+    }
+
     fn open_struct(&mut self, _: Option<&'static str>) -> Result<(), Self::Error> {
         self.open_object()
     }
@@ -122,15 +223,41 @@ impl<Writer: TextWriter> Outliner for TextSerializer<Writer> {
                 self.writer.write_str(indent)?;
             }
         }
-        self.writer.write_char(']')
+        self.writer.write_char(']').map_err(SerializeError::Writer)
+    }
+
+    fn push_key(&mut self) -> Result<(), Self::Error> {
+        assert!(!self.at_key, "{}", INVALID_STATE_ERROR);
+        self.at_key = true;
+        Ok(())
+    }
+
+    fn push_value(&mut self) -> Result<(), Self::Error> {
+        match self.maps.last().expect(NOT_MAP_ERROR) {
+            MapState::Object => Ok(()),
+            MapState::Array { .. } => self.push_item(),
+        }
+    }
+
+    fn close_map(&mut self) -> Result<(), Self::Error> {
+        match self.maps.pop().expect(NOT_MAP_ERROR) {
+            MapState::Object => self.close_object(),
+            MapState::Array { pair_open } => {
+                if pair_open {
+                    self.close_list()?;
+                }
+                self.close_list()
+            }
+        }
     }
 }
 
 impl<Writer: TextWriter> JsonOutliner for TextSerializer<Writer> {
     fn open_object(&mut self) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         self.depth += 1;
         self.at_first = true;
-        self.writer.write_char('{')
+        self.writer.write_char('{').map_err(SerializeError::Writer)
     }
 
     fn push_entry(&mut self, key: &str) -> Result<(), Self::Error> {
@@ -144,74 +271,101 @@ impl<Writer: TextWriter> JsonOutliner for TextSerializer<Writer> {
         self.depth -= 1;
         if self.at_first {
             self.at_first = false;
-            self.writer.write_char('}')
+            self.writer.write_char('}').map_err(SerializeError::Writer)
         } else if let Some(indent) = self.config.indent {
             self.writer.write_char('\n')?;
             for _ in 0..self.depth {
                 self.writer.write_str(indent)?;
             }
-            self.writer.write_char('}')
+            self.writer.write_char('}').map_err(SerializeError::Writer)
         } else {
-            self.writer.write_str(" }")
+            self.writer.write_str(" }").map_err(SerializeError::Writer)
         }
     }
 }
 
 impl<Writer: TextWriter> Serializer for TextSerializer<Writer> {
     fn put_bool(&mut self, value: bool) -> Result<(), Self::Error> {
-        self.writer.write_str(if value { "true" } else { "false" })
+        self.begin_value(false)?;
+        self.writer.write_str(if value { "true" } else { "false" }).map_err(SerializeError::Writer)
     }
 
     fn put_i8(&mut self, value: i8) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_i16(&mut self, value: i16) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_i32(&mut self, value: i32) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
+        let mut buffer = itoa::Buffer::new();
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
+    }
+
+    fn put_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_u8(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_u16(&mut self, value: u16) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_u32(&mut self, value: u32) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         let mut buffer = itoa::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
+    }
+
+    fn put_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
+        let mut buffer = itoa::Buffer::new();
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_f32(&mut self, value: f32) -> Result<(), Self::Error> {
-        // TODO: Special cases
+        if !value.is_finite() {
+            return Err(SerializeError::NonFiniteFloat);
+        }
+        self.begin_value(false)?;
         let mut buffer = ryu::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        // TODO: Special cases
+        if !value.is_finite() {
+            return Err(SerializeError::NonFiniteFloat);
+        }
+        self.begin_value(false)?;
         let mut buffer = ryu::Buffer::new();
-        self.writer.write_str(buffer.format(value))
+        self.writer.write_str(buffer.format(value)).map_err(SerializeError::Writer)
     }
 
     fn put_char(&mut self, value: char) -> Result<(), Self::Error> {
@@ -231,6 +385,11 @@ impl<Writer: TextWriter> Serializer for TextSerializer<Writer> {
             '\t' => self.writer.write_str("\\t"),
             value => self.writer.write_char(value),
         }
+        .map_err(SerializeError::Writer)
+    }
+
+    fn append_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.bytes.write(&mut self.writer, value).map_err(SerializeError::Writer)
     }
 
     fn put_tag(
@@ -246,30 +405,95 @@ impl<Writer: TextWriter> Serializer for TextSerializer<Writer> {
         let _ = len;
         self.open_list_streaming()
     }
+
+    fn open_map_sized(&mut self, len: usize, str_keys: bool) -> Result<(), Self::Error> {
+        let _ = len;
+        self.open_map_unsized(str_keys)
+    }
+
+    fn supports_unsized(&self) -> bool {
+        true
+    }
+
+    fn open_list_unsized(&mut self) -> Result<(), Self::Error> {
+        self.open_list_streaming()
+    }
+
+    fn open_map_unsized(&mut self, str_keys: bool) -> Result<(), Self::Error> {
+        if str_keys {
+            self.open_object()?;
+            self.maps.push(MapState::Object);
+        } else {
+            self.open_list_streaming()?;
+            self.maps.push(MapState::Array { pair_open: false });
+        }
+        Ok(())
+    }
+
+    fn error(&self, source: Box<dyn std::error::Error + Send + Sync>) -> Self::Error {
+        SerializeError::Custom(source)
+    }
 }
 
 impl<Writer: TextWriter> JsonSerializer for TextSerializer<Writer> {
     fn open_list_streaming(&mut self) -> Result<(), Self::Error> {
+        self.begin_value(false)?;
         self.depth += 1;
         self.at_first = true;
-        self.writer.write_char('[')
+        self.writer.write_char('[').map_err(SerializeError::Writer)
     }
 
     fn add_entry(&mut self) -> Result<(), Self::Error> {
-        if self.at_first {
-            self.at_first = false;
-        } else {
-            self.writer.write_char(',')?;
-        }
-        if let Some(indent) = self.config.indent {
-            self.writer.write_char('\n')?;
-            for _ in 0..self.depth {
-                self.writer.write_str(indent)?;
+        self.begin_entry()?;
+        self.in_key = true;
+        self.writer.write_char('\"').map_err(SerializeError::Writer)
+    }
+}
+
+/// Describes an error that can occur when serializing JSON.
+#[derive(Debug)]
+pub enum SerializeError<WriterError> {
+    /// An error from the underlying [`TextWriter`].
+    Writer(WriterError),
+
+    /// An error raised by a [`serdere::Serialize`] implementation using [`Serializer::error`].
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+
+    /// A map which was opened with `str_keys` set (see [`Serializer::open_map_sized`]) was given
+    /// a key that isn't a string.
+    NonStringKey,
+
+    /// A floating-point number was infinite or NaN, which can't be represented in JSON.
+    NonFiniteFloat,
+}
+
+impl<WriterError> From<WriterError> for SerializeError<WriterError> {
+    fn from(err: WriterError) -> Self {
+        Self::Writer(err)
+    }
+}
+
+impl<WriterError: std::fmt::Display> std::fmt::Display for SerializeError<WriterError> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializeError::Writer(err) => err.fmt(f),
+            SerializeError::Custom(source) => source.fmt(f),
+            SerializeError::NonStringKey => {
+                f.write_str("map with string keys can't have a non-string key")
+            }
+            SerializeError::NonFiniteFloat => {
+                f.write_str("JSON can't represent a non-finite number")
             }
-        } else {
-            self.writer.write_char(' ')?;
         }
-        self.in_key = true;
-        self.writer.write_char('\"')
+    }
+}
+
+impl<WriterError: std::error::Error + 'static> std::error::Error for SerializeError<WriterError> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SerializeError::Writer(err) => Some(err),
+            SerializeError::Custom(source) => Some(&**source),
+            SerializeError::NonStringKey | SerializeError::NonFiniteFloat => None,
+        }
     }
 }
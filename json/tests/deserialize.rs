@@ -1,7 +1,7 @@
 #![allow(clippy::bool_assert_comparison)]
-use serdere_json::{from_str, DeserializeError, TextDeserializerConfig, ValueExt};
+use serdere_json::{from_str, from_str_into, DeserializeError, TextDeserializerConfig, ValueExt};
 use serdere_json::{JsonDeserializer, JsonOutliner, TextDeserializer};
-use serdere::{Deserialize, Deserializer, Outliner, Value};
+use serdere::{Bytes, Deserialize, Deserializer, Outliner, Value};
 
 #[test]
 fn test_bool() {
@@ -40,6 +40,51 @@ fn test_number() {
     assert_eq!(from_str::<f32>("-1.0e-4").unwrap(), -1.0e-4);
     assert_eq!(from_str::<f32>("-0.125").unwrap(), -0.125);
     assert!(from_str::<f32>("-0e5").unwrap().is_sign_negative());
+    assert_eq!(
+        from_str::<u128>("340282366920938463463374607431768211455").unwrap(),
+        u128::MAX
+    );
+    assert!(from_str::<u128>("340282366920938463463374607431768211456").is_err());
+    assert_eq!(
+        from_str::<i128>("-170141183460469231731687303715884105728").unwrap(),
+        i128::MIN
+    );
+    assert_eq!(
+        from_str::<i128>("123456789012345678901234567890").unwrap(),
+        123456789012345678901234567890
+    );
+    assert!(from_str::<std::num::NonZeroU128>("0").is_err());
+}
+
+#[test]
+fn test_bytes() {
+    assert_eq!(from_str::<Bytes>("\"\"").unwrap(), Bytes(vec![]));
+    assert_eq!(from_str::<Bytes>("\"Zg==\"").unwrap(), Bytes(b"f".to_vec()));
+    assert_eq!(from_str::<Bytes>("\"Zm8=\"").unwrap(), Bytes(b"fo".to_vec()));
+    assert_eq!(from_str::<Bytes>("\"Zm9vYmFy\"").unwrap(), Bytes(b"foobar".to_vec()));
+    assert_eq!(from_str::<Bytes>("\"Zm9vYg\"").unwrap(), Bytes(b"foob".to_vec()));
+    assert_eq!(from_str::<Bytes>("\"-_8\"").unwrap(), Bytes(vec![0xFB, 0xFF]));
+    assert!(from_str::<Bytes>("\"Zm9vY\"").is_err());
+    assert!(from_str::<Bytes>("\"Zg=a\"").is_err());
+    assert!(from_str::<Bytes>("\"Zm9v!\"").is_err());
+
+    let big: Vec<u8> = (0..5000u32).map(|i| (i * 7) as u8).collect();
+    let source = serdere_json::to_str(&Bytes(&big[..])).unwrap();
+    assert_eq!(from_str::<Bytes>(&source).unwrap(), Bytes(big));
+
+    let from_hex = |source| {
+        let mut d = TextDeserializer::new(
+            TextDeserializerConfig {
+                bytes_encoding: serdere_json::BytesEncoding::Hex,
+                ..Default::default()
+            },
+            source,
+        )?;
+        Value::with(&mut d, |value| Ok(value.get_bytes()?.into_owned()))
+    };
+    assert_eq!(from_hex("\"00ff7A\"").unwrap(), [0x00, 0xFF, 0x7A]);
+    assert!(from_hex("\"abc\"").is_err());
+    assert!(from_hex("\"Zg==\"").is_err());
 }
 
 #[test]
@@ -58,6 +103,179 @@ fn test_option() {
     );
 }
 
+#[test]
+fn test_std_types() {
+    use std::cmp::Ordering;
+    use std::ops::Bound;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    assert_eq!(
+        from_str::<Duration>(r#"{ "secs": 3, "nanos": 500 }"#).unwrap(),
+        Duration::new(3, 500)
+    );
+    assert!(from_str::<Duration>(r#"{ "secs": 3, "nanos": 1000000000 }"#).is_err());
+    assert_eq!(
+        from_str::<SystemTime>(r#"{ "secs": 10, "nanos": 1 }"#).unwrap(),
+        UNIX_EPOCH + Duration::new(10, 1)
+    );
+    assert_eq!(
+        from_str::<SystemTime>(r#"{ "secs": -2, "nanos": 250000000 }"#).unwrap(),
+        UNIX_EPOCH - Duration::new(1, 750_000_000)
+    );
+    assert_eq!(from_str::<std::ops::Range<u32>>(r#"{ "start": 1, "end": 4 }"#).unwrap(), 1..4);
+    assert_eq!(
+        from_str::<std::ops::RangeInclusive<u32>>(r#"{ "start": 1, "end": 4 }"#).unwrap(),
+        1..=4
+    );
+    assert_eq!(
+        from_str::<Bound<u32>>(r#"{ "type": "Excluded", "value": 3 }"#).unwrap(),
+        Bound::Excluded(3)
+    );
+    assert_eq!(
+        from_str::<Bound<u32>>(r#"{ "type": "Unbounded" }"#).unwrap(),
+        Bound::Unbounded
+    );
+    assert_eq!(
+        from_str::<Result<u32, String>>(r#"{ "type": "Ok", "value": 3 }"#).unwrap(),
+        Ok(3)
+    );
+    assert_eq!(
+        from_str::<Result<u32, String>>(r#"{ "type": "Err", "value": "bad" }"#).unwrap(),
+        Err("bad".to_string())
+    );
+    assert!(from_str::<Result<u32, String>>(r#"{ "type": "Maybe" }"#).is_err());
+    assert_eq!(from_str::<Ordering>("\"Greater\"").unwrap(), Ordering::Greater);
+}
+
+#[test]
+fn test_string_types() {
+    use serdere::CharRange;
+    use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+    use std::path::PathBuf;
+    assert_eq!(
+        from_str::<IpAddr>("\"127.0.0.1\"").unwrap(),
+        IpAddr::from([127, 0, 0, 1])
+    );
+    assert_eq!(from_str::<Ipv6Addr>("\"::1\"").unwrap(), Ipv6Addr::LOCALHOST);
+    assert_eq!(
+        from_str::<SocketAddr>("\"10.0.0.1:8080\"").unwrap(),
+        SocketAddr::from(([10, 0, 0, 1], 8080))
+    );
+    assert_eq!(
+        from_str::<IpAddr>("\"300.0.0.1\"").unwrap_err().message().to_string(),
+        "invalid IP address syntax"
+    );
+    assert_eq!(
+        from_str::<PathBuf>("\"/etc/hosts\"").unwrap(),
+        PathBuf::from("/etc/hosts")
+    );
+    assert!(from_str::<PathBuf>("\"\u{FFFF}xyz\"").is_err());
+    assert_eq!(from_str::<CharRange>("\"a-z\"").unwrap(), CharRange('a'..='z'));
+    assert_eq!(from_str::<CharRange>("\"--0\"").unwrap(), CharRange('-'..='0'));
+    assert_eq!(
+        from_str::<CharRange>("\"a-zz\"").unwrap_err().message().to_string(),
+        "invalid character range syntax"
+    );
+    assert!(from_str::<CharRange>("\"az\"").is_err());
+    assert!(from_str::<char>("\"ab\"").is_err());
+    assert!(from_str::<char>("\"\"").is_err());
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+        assert_eq!(
+            from_str::<OsString>("\"\u{FFFF}61ff62\"").unwrap(),
+            OsString::from_vec(b"a\xFFb".to_vec())
+        );
+    }
+}
+
+#[test]
+fn test_borrowed_str() {
+    let source = "\"abc\"";
+    let str = from_str::<&str>(source).unwrap();
+    assert_eq!(str, "abc");
+    assert_eq!(str.as_ptr(), source[1..].as_ptr());
+    assert_eq!(
+        from_str::<&str>("\"a\\nb\"").unwrap_err().message().to_string(),
+        "string can't be borrowed from the input"
+    );
+    assert_eq!(
+        from_str::<Vec<&str>>(r#"["a", "bc", ""]"#).unwrap(),
+        vec!["a", "bc", ""]
+    );
+
+    // Strings from lookback data should still be borrowed
+    let source = r#"{ "b": "second", "a": "first" }"#;
+    let mut d = TextDeserializer::new(Default::default(), source).unwrap();
+    let (a, b) = Value::with(&mut d, |value| {
+        let mut st = value.into_struct(None, None)?;
+        let a = st.field("a")?.get::<&str>()?;
+        let b = st.field("b")?.get::<&str>()?;
+        st.close()?;
+        Ok((a, b))
+    })
+    .unwrap();
+    assert_eq!((a, b), ("first", "second"));
+    assert_eq!(b.as_ptr(), source[8..].as_ptr());
+}
+
+#[test]
+fn test_in_place() {
+    let mut list: Vec<u32> = Vec::with_capacity(16);
+    let list_ptr = list.as_ptr();
+    from_str_into("[1, 2, 3]", &mut list).unwrap();
+    assert_eq!(list, [1, 2, 3]);
+    from_str_into("[4]", &mut list).unwrap();
+    assert_eq!(list, [4]);
+    assert_eq!(list.as_ptr(), list_ptr);
+
+    let mut str = String::with_capacity(16);
+    let str_ptr = str.as_ptr();
+    from_str_into("\"abc\"", &mut str).unwrap();
+    assert_eq!(str, "abc");
+    assert_eq!(str.as_ptr(), str_ptr);
+
+    let mut array = [String::with_capacity(16), String::new()];
+    let array_ptr = array[0].as_ptr();
+    from_str_into(r#"["x", "y"]"#, &mut array).unwrap();
+    assert_eq!(array, ["x", "y"]);
+    assert_eq!(array[0].as_ptr(), array_ptr);
+    assert!(from_str_into(r#"["x"]"#, &mut array).is_err());
+}
+
+#[test]
+fn test_wrappers() {
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    assert_eq!(from_str::<Box<u32>>("1").unwrap(), Box::new(1));
+    assert_eq!(&*from_str::<Rc<str>>("\"abc\"").unwrap(), "abc");
+    assert_eq!(&*from_str::<Arc<str>>("\"abc\"").unwrap(), "abc");
+    assert_eq!(&*from_str::<Box<str>>("\"abc\"").unwrap(), "abc");
+    assert_eq!(from_str::<Cow<str>>("\"abc\"").unwrap(), "abc");
+    assert_eq!(*from_str::<Mutex<u32>>("5").unwrap().lock().unwrap(), 5);
+    assert_eq!(
+        from_str::<std::cmp::Reverse<u32>>("5").unwrap(),
+        std::cmp::Reverse(5)
+    );
+    assert_eq!(
+        from_str::<std::num::Wrapping<u8>>("5").unwrap(),
+        std::num::Wrapping(5)
+    );
+    assert_eq!(
+        from_str::<std::marker::PhantomData<u8>>("{}").unwrap(),
+        std::marker::PhantomData
+    );
+
+    // `null` optimization should pass through wrappers
+    assert_eq!(from_str::<Option<Box<u32>>>("null").unwrap(), None);
+    assert_eq!(
+        from_str::<Option<Rc<Option<u32>>>>("{ \"has_value\": true, \"value\": null }").unwrap(),
+        Some(Rc::new(None))
+    );
+}
+
 #[test]
 fn test_enum() {
     use serdere::{Deserialize, FixedNameMap, NameMap};
@@ -91,11 +309,115 @@ fn test_list() {
     );
 }
 
+#[test]
+fn test_collections() {
+    use std::collections::{BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
+    assert_eq!(*from_str::<Box<[u32]>>("[1, 2, 3]").unwrap(), [1, 2, 3]);
+    assert_eq!(
+        from_str::<VecDeque<u32>>("[1, 2, 3]").unwrap(),
+        VecDeque::from([1, 2, 3])
+    );
+    assert_eq!(
+        from_str::<LinkedList<u32>>("[1, 2, 3]").unwrap(),
+        LinkedList::from([1, 2, 3])
+    );
+    assert_eq!(
+        from_str::<BinaryHeap<u32>>("[2, 3, 1]").unwrap().into_sorted_vec(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        from_str::<HashSet<u32>>("[3, 1, 2]").unwrap(),
+        HashSet::from([1, 2, 3])
+    );
+    assert_eq!(
+        from_str::<BTreeSet<u32>>("[3, 1, 2]").unwrap(),
+        BTreeSet::from([1, 2, 3])
+    );
+    let err = from_str::<HashSet<u32>>("[1, 2, 1]").unwrap_err();
+    assert_eq!(err.message().to_string(), "set contains a duplicate item");
+    assert!(from_str::<BTreeSet<u32>>("[1, 2, 2]").is_err());
+}
+
 #[test]
 fn test_tuple() {
     assert_eq!(from_str::<[u32; 3]>("[3, 6, 9]").unwrap(), [3, 6, 9]);
     assert_eq!(from_str::<(u32, bool)>("[3, false]").unwrap(), (3, false));
     assert!(from_str::<[u32; 4]>("[3, 6, 9]").is_err());
+    assert_eq!(from_str::<(u32,)>("[3]").unwrap(), (3,));
+    type Long = (u8, u16, u32, u64, i8, i16, i32, i64, bool, String, f32, f64, (), bool, u8, u8);
+    let long = from_str::<Long>(
+        r#"[1, 2, 3, 4, -5, -6, -7, -8, true, "x", 1.5, 2.5, {}, false, 15, 16]"#,
+    )
+    .unwrap();
+    assert_eq!((long.0, long.3, long.7, long.8), (1, 4, -8, true));
+    assert_eq!((long.9.as_str(), long.11, long.13, long.15), ("x", 2.5, false, 16));
+
+    // Manual implementation using `inline_get`
+    #[derive(PartialEq, Debug)]
+    struct Test {
+        id: u32,
+        name: String,
+        flag: bool,
+    }
+    impl<D: Deserializer + ?Sized> Deserialize<D> for Test {
+        const NULLABLE: bool = false;
+        fn deserialize(value: Value<D>, _: &mut ()) -> Result<Self, D::Error> {
+            let mut tuple = value.into_tuple(None)?;
+            let (id, name, flag) = tuple.inline_get::<(u32, String, bool)>()?;
+            tuple.close()?;
+            Ok(Test { id, name, flag })
+        }
+    }
+    assert_eq!(
+        from_str::<Test>(r#"[7, "seven", true]"#).unwrap(),
+        Test {
+            id: 7,
+            name: "seven".to_string(),
+            flag: true
+        }
+    );
+}
+
+#[test]
+fn test_map() {
+    use std::collections::{BTreeMap, HashMap};
+    assert_eq!(
+        from_str::<BTreeMap<String, u32>>(r#"{ "a": 1, "b": 2 }"#).unwrap(),
+        BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+    );
+    assert_eq!(
+        from_str::<HashMap<u32, bool>>("[[1, true], [2, false]]").unwrap(),
+        HashMap::from([(1, true), (2, false)])
+    );
+    assert_eq!(
+        from_str::<BTreeMap<(u32, u32), String>>(r#"[[[1, 2], "x"]]"#).unwrap(),
+        BTreeMap::from([((1, 2), "x".to_string())])
+    );
+    assert!(from_str::<BTreeMap<u32, u32>>("{}").unwrap().is_empty());
+    assert!(from_str::<BTreeMap<String, u32>>("[]").unwrap().is_empty());
+    assert!(from_str::<BTreeMap<u32, u32>>(r#"{ "1": 2 }"#).is_err());
+    assert!(from_str::<BTreeMap<u32, u32>>("[[1]]").is_err());
+    assert!(from_str::<BTreeMap<u32, u32>>("[[1, 2, 3]]").is_err());
+    assert!(from_str::<BTreeMap<u32, u32>>("[1, 2]").is_err());
+    let err = from_str::<HashMap<String, u32>>(r#"{ "a": 1, "a": 2 }"#).unwrap_err();
+    assert_eq!(err.message().to_string(), "map contains a duplicate key");
+    assert!(from_str::<BTreeMap<u32, u32>>("[[1, 2], [1, 3]]").is_err());
+
+    // Maps read from lookback data
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Test {
+        a: u32,
+        b: BTreeMap<String, u32>,
+        c: BTreeMap<u32, u32>,
+    }
+    assert_eq!(
+        from_str::<Test>(r#"{ "c": [[3, 4]], "b": { "x": 1, "y": 2 }, "a": 5 }"#).unwrap(),
+        Test {
+            a: 5,
+            b: BTreeMap::from([("x".to_string(), 1), ("y".to_string(), 2)]),
+            c: BTreeMap::from([(3, 4)]),
+        }
+    );
 }
 
 #[test]
@@ -110,6 +432,7 @@ fn test_object_simple() {
     let mut d = TextDeserializer::new(
         TextDeserializerConfig {
             allow_comments: true,
+            ..Default::default()
         },
         source,
     )
@@ -310,6 +633,69 @@ fn test_derive_struct() {
     );
 }
 
+#[test]
+fn test_derive_borrow() {
+    use std::borrow::Cow;
+    #[derive(PartialEq, Eq, Debug, Deserialize)]
+    struct Test<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        desc: Cow<'a, str>,
+        #[serde(borrow = "'a")]
+        note: Option<Cow<'a, str>>,
+    }
+    let is_borrowed = |str: &Cow<str>| matches!(str, Cow::Borrowed(_));
+
+    // Streaming
+    let source = r#"{ "name": "Mike", "desc": "plain", "note": null }"#;
+    let test = from_str::<Test>(source).unwrap();
+    assert_eq!(test.name, "Mike");
+    assert!(is_borrowed(&test.desc));
+    assert_eq!(test.note, None);
+
+    // Lookback
+    let source = r#"{ "note": "read early", "desc": "esc\naped", "name": "Mike" }"#;
+    let test = from_str::<Test>(source).unwrap();
+    assert_eq!(test.name, "Mike");
+    assert_eq!(test.desc, "esc\naped");
+    assert!(!is_borrowed(&test.desc));
+    assert_eq!(test.note.as_deref(), Some("read early"));
+    assert!(is_borrowed(test.note.as_ref().unwrap()));
+}
+
+#[test]
+fn test_derive_in_place() {
+    #[derive(PartialEq, Eq, Debug, Deserialize)]
+    struct Test {
+        name: String,
+        #[serde(proxy = u32)]
+        age: u64,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+    let mut test = Test {
+        name: String::with_capacity(64),
+        age: 0,
+        tags: vec![String::with_capacity(64)],
+    };
+    let name_ptr = test.name.as_ptr();
+    let tag_ptr = test.tags[0].as_ptr();
+    from_str_into(r#"{ "name": "Mike", "age": 28, "tags": ["a", "b"] }"#, &mut test).unwrap();
+    assert_eq!(
+        test,
+        Test {
+            name: "Mike".to_string(),
+            age: 28,
+            tags: vec!["a".to_string(), "b".to_string()]
+        }
+    );
+    assert_eq!(test.name.as_ptr(), name_ptr);
+    assert_eq!(test.tags[0].as_ptr(), tag_ptr);
+    from_str_into(r#"{ "name": "Jo", "age": 3 }"#, &mut test).unwrap();
+    assert_eq!(test.tags, Vec::<String>::new());
+    assert_eq!(test.name.as_ptr(), name_ptr);
+}
+
 #[test]
 fn test_derive_enum_simple() {
     #[derive(PartialEq, Eq, Debug, Deserialize)]
@@ -448,3 +834,531 @@ fn test_derive_enum_transparent() {
         }
     );
 }
+
+#[test]
+fn test_transcode() {
+    use serdere::{transcode, Tree, TreeDeserializer, TreeSerializer};
+    use serdere_json::{reformat, TextSerializer, TextSerializerConfig};
+    let reformat = |source: &str, indent: Option<&'static str>| {
+        let mut res = String::new();
+        let config = TextSerializerConfig {
+            indent,
+            ..Default::default()
+        };
+        reformat(source, &mut res, config).unwrap();
+        res
+    };
+    let pretty = "{\n  \"name\": \"\\ttest\",\n  \"values\": [\n    1,\n    -2,\n    \
+        3.5,\n    340282366920938463463374607431768211455\n  ],\n  \"extra\": {\n    \
+        \"flag\": true,\n    \"nothing\": null,\n    \"empty\": []\n  }\n}";
+    let compact = "{ \"name\": \"\\ttest\", \"values\": [1, -2, 3.5, \
+        340282366920938463463374607431768211455], \"extra\": { \"flag\": true, \
+        \"nothing\": null, \"empty\": [] } }";
+    assert_eq!(reformat(pretty, None), compact);
+    assert_eq!(reformat(compact, Some("  ")), pretty);
+
+    // Numbers keep their type, and non-finite numbers are rejected
+    assert_eq!(
+        reformat("[1.0, -0.0, 2.50, 1.5e3, 10.00, 7, -0]", None),
+        "[1.0, -0.0, 2.5, 1500.0, 10.0, 7, 0]"
+    );
+    let mut res = String::new();
+    let err = serdere_json::reformat("[1e400]", &mut res, TextSerializerConfig::default());
+    assert!(matches!(
+        err,
+        Err(serdere::TranscodeError::Serialize(
+            serdere_json::SerializeError::NonFiniteFloat
+        ))
+    ));
+
+    // Transcode to another format
+    let mut d = TextDeserializer::new(TextDeserializerConfig::default(), "[1, -2, 3.5]").unwrap();
+    let mut s = TreeSerializer::new();
+    let (mut d_done_flag, mut s_done_flag) = (false, false);
+    transcode(
+        Value::new(&mut d, &mut d_done_flag),
+        Value::new(&mut s, &mut s_done_flag),
+    )
+    .unwrap();
+    d.close().unwrap();
+    assert_eq!(
+        s.finish(),
+        Tree::List(vec![Tree::U64(1), Tree::I64(-2), Tree::F64(3.5)])
+    );
+
+    // Read a tree without a type to guide deserialization
+    assert_eq!(
+        from_str::<Tree>(r#"{ "a": [1, -2.5], "b": null }"#).unwrap(),
+        Tree::Map(vec![
+            (
+                Tree::Str("a".into()),
+                Tree::List(vec![Tree::U64(1), Tree::F64(-2.5)])
+            ),
+            (Tree::Str("b".into()), Tree::Null)
+        ])
+    );
+
+    // Transcode from a tree
+    let tree = Tree::Struct {
+        type_name: Some("Point"),
+        fields: vec![
+            (
+                "pos",
+                Tree::Tuple {
+                    type_name: None,
+                    elements: vec![Tree::U8(1), Tree::I8(-2)],
+                },
+            ),
+            ("grade", Tree::Char('A')),
+            (
+                "mode",
+                Tree::Tag {
+                    max_index: 1,
+                    index: 1,
+                    name: Some("fast"),
+                },
+            ),
+            (
+                "level",
+                Tree::Tag {
+                    max_index: 3,
+                    index: 2,
+                    name: None,
+                },
+            ),
+        ],
+    };
+    let mut res = String::new();
+    let mut d = TreeDeserializer::new(&tree);
+    let mut s = TextSerializer::new(TextSerializerConfig::default(), &mut res);
+    let (mut d_done_flag, mut s_done_flag) = (false, false);
+    transcode(
+        Value::new(&mut d, &mut d_done_flag),
+        Value::new(&mut s, &mut s_done_flag),
+    )
+    .unwrap();
+    s.close();
+    assert_eq!(
+        res,
+        r#"{ "pos": [1, -2], "grade": "A", "mode": "fast", "level": 2 }"#
+    );
+}
+
+#[test]
+fn test_record() {
+    use serdere::{Event, Recorder, Replayer};
+    use std::collections::HashMap;
+    fn read<D: Deserializer>(value: Value<D>) -> Result<HashMap<String, Vec<String>>, D::Error> {
+        let mut map = value.into_map()?;
+        let mut res = HashMap::new();
+        while let Some(key) = map.next_key()? {
+            let key = key.get::<String>()?;
+            let value = map.value()?;
+            let value = if key == "tags" {
+                value.get()?
+            } else {
+                vec![value.get::<String>()?]
+            };
+            res.insert(key, value);
+        }
+        Ok(res)
+    }
+    let source = r#"{ "name": "Bob", "tags": ["a"] }"#;
+    let d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+    let mut d = Recorder::new(d);
+    let value = Value::with(&mut d, read).unwrap();
+    let (d, log) = d.into_parts();
+    d.close().unwrap();
+    assert_eq!(log[0].event, Event::OpenMap(None));
+    assert_eq!(log.last().unwrap().event, Event::NextKey(false));
+    assert!(log.iter().all(|record| record.depth <= 2));
+
+    // Replay the log as a deserializer
+    let mut d = Replayer::new(&log);
+    let replayed = Value::with(&mut d, read).unwrap();
+    d.close().unwrap();
+    assert_eq!(replayed, value);
+}
+
+#[test]
+fn test_checker() {
+    use serdere::Checker;
+    use std::collections::BTreeMap;
+    let source = r#"{ "a": [[1, null], [2, "x"]], "b": [] }"#;
+    let d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+    let mut d = Checker::new(d);
+    let value: BTreeMap<String, Vec<(u32, Option<String>)>> =
+        Value::with(&mut d, |value| value.get()).unwrap();
+    d.finish().close().unwrap();
+    assert_eq!(value["a"], [(1, None), (2, Some("x".to_owned()))]);
+    assert!(value["b"].is_empty());
+}
+
+#[test]
+fn test_erased() {
+    use serdere::ErasedDeserializer;
+    let mut d = TextDeserializer::new(TextDeserializerConfig::default(), "[1, 2, 3]").unwrap();
+    let mut erased = d.erased();
+    let erased: &mut dyn ErasedDeserializer = &mut erased;
+    let value: Vec<u32> = Value::with(erased, |value| value.get()).unwrap();
+    d.close().unwrap();
+    assert_eq!(value, [1, 2, 3]);
+
+    // Errors are converted to `DeserializeError<String>`
+    let mut d = TextDeserializer::new(TextDeserializerConfig::default(), "[1, true]").unwrap();
+    let err = Value::with(&mut d.erased() as &mut dyn ErasedDeserializer, |value| {
+        value.get::<Vec<u32>>()
+    })
+    .unwrap_err();
+    let err = err.downcast::<DeserializeError<String>>().unwrap();
+    assert_eq!(err.position(), "around \"true]\"");
+}
+
+#[test]
+fn test_path_tracker() {
+    use serdere::{PathError, PathSegment, PathTracker, StrPosition};
+    use std::collections::HashMap;
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        servers: Vec<Server>,
+        limits: HashMap<String, u32>,
+    }
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Root {
+        config: Config,
+    }
+    fn read(source: &str) -> PathError<DeserializeError<StrPosition<'_>>> {
+        let d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+        let mut d = PathTracker::new(d);
+        Value::with(&mut d, |value| value.get::<Root>()).unwrap_err()
+    }
+    let err = read(
+        r#"{ "config": { "servers": [
+            { "host": "a", "port": 80 },
+            { "host": "b", "port": 81 },
+            { "host": "c", "port": "x" }
+        ], "limits": {} } }"#,
+    );
+    assert_eq!(err.path().to_string(), "config.servers[2].port");
+    assert_eq!(
+        err.path().segments(),
+        [
+            PathSegment::Field("config"),
+            PathSegment::Field("servers"),
+            PathSegment::Index(2),
+            PathSegment::Field("port")
+        ]
+    );
+    assert!(err.to_string().starts_with("config.servers[2].port: "));
+    let err = read(r#"{ "config": { "servers": [], "limits": { "max conns": -1 } } }"#);
+    assert_eq!(err.path().to_string(), "config.limits[\"max conns\"]");
+}
+
+#[test]
+fn test_collect_errors() {
+    use serdere::{ErrorCollector, PathError, PathTracker, StrPosition};
+    use std::collections::HashMap;
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+        port: u16,
+        servers: Vec<Server>,
+        limits: HashMap<String, u32>,
+        debug: bool,
+    }
+    type Error<'a> = PathError<DeserializeError<StrPosition<'a>>>;
+    fn read(source: &str) -> Result<Config, Vec<Error<'_>>> {
+        let d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+        let mut d = ErrorCollector::new(PathTracker::new(d));
+        let res = Value::with(&mut d, |value| value.get::<Config>());
+        let (config, d) = d.finish(res)?;
+        d.into_inner().close().unwrap();
+        Ok(config)
+    }
+    let config = read(
+        r#"{ "name": "svc", "port": 80, "servers": [{ "host": "a", "port": 81 }],
+            "limits": { "conns": 10 }, "debug": false }"#,
+    )
+    .unwrap();
+    assert_eq!(config.servers[0].port, 81);
+
+    // All errors are reported, including for fields which are read out of order
+    let errors = read(
+        r#"{ "debug": "yes", "name": "svc", "port": "x", "servers": [
+            { "host": "a", "port": 81 },
+            { "port": -1 },
+            { "host": "c", "port": 83 }
+        ], "limits": { "conns": 10, "max conns": "many" } }"#,
+    )
+    .unwrap_err();
+    let paths: Vec<String> = errors.iter().map(|err| err.path().to_string()).collect();
+    assert_eq!(
+        paths,
+        [
+            "port",
+            "servers[1].host",
+            "servers[1].port",
+            "limits[\"max conns\"]",
+            "debug"
+        ]
+    );
+    assert!(errors[0].inner().position().as_str().starts_with("\"x\""));
+    assert!(errors[4].inner().position().as_str().starts_with("\"yes\""));
+
+    // Errors which leave the input in an unknown state stop deserialization
+    let errors = read(r#"{ "name": "svc", "port": "x", "servers": [ } "#).unwrap_err();
+    let paths: Vec<String> = errors.iter().map(|err| err.path().to_string()).collect();
+    assert_eq!(paths, ["port", "servers[0]"]);
+
+    // Recovery passes through wrappers such as `Recorder`
+    let source = r#"{ "name": 1, "port": "x", "servers": [], "limits": {}, "debug": false }"#;
+    let d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+    let mut d = serdere::Recorder::new(ErrorCollector::new(PathTracker::new(d)));
+    let res = Value::with(&mut d, |value| value.get::<Config>());
+    let (d, log) = d.into_parts();
+    assert!(log.iter().all(|record| record.depth <= 1));
+    let errors = d.finish(res).err().unwrap();
+    let paths: Vec<String> = errors.iter().map(|err| err.path().to_string()).collect();
+    assert_eq!(paths, ["name", "port"]);
+}
+
+#[test]
+fn test_peek_kind() {
+    use serdere::{Number, ShapeDeserializer, ValueKind};
+    #[derive(Debug, PartialEq)]
+    enum Id {
+        Index(u64),
+        Name(String),
+    }
+    impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for Id {
+        const NULLABLE: bool = false;
+        fn deserialize(mut value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+            match value.peek_kind()? {
+                ValueKind::Str => Ok(Id::Name(value.get_str()?.into_owned())),
+                _ => Ok(Id::Index(value.get_u64()?)),
+            }
+        }
+    }
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Pair {
+        first: Id,
+        second: Id,
+    }
+    assert_eq!(
+        from_str::<Vec<Id>>(r#"[3, "four"]"#).unwrap(),
+        [Id::Index(3), Id::Name("four".to_owned())]
+    );
+
+    // Fields which are read out of order are peeked from the lookback buffer
+    assert_eq!(
+        from_str::<Pair>(r#"{ "second": 2, "first": "one" }"#).unwrap(),
+        Pair {
+            first: Id::Name("one".to_owned()),
+            second: Id::Index(2)
+        }
+    );
+
+    fn kinds(source: &str) -> Vec<ValueKind> {
+        let mut d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+        let mut kinds = Vec::new();
+        d.open_object().unwrap();
+        for key in ["a", "b", "c", "d", "e", "f", "g"] {
+            assert!(d.try_push_entry(key).unwrap());
+            kinds.push(d.peek_kind().unwrap());
+            d.skip_value().unwrap();
+        }
+        d.close_object().unwrap();
+        kinds
+    }
+    let source = r#"{ "g": 1.5, "f": 10, "e": [], "d": {}, "c": "x", "b": true, "a": null }"#;
+    assert_eq!(
+        kinds(source),
+        [
+            ValueKind::Null,
+            ValueKind::Bool,
+            ValueKind::Str,
+            ValueKind::Struct,
+            ValueKind::List,
+            ValueKind::Integer,
+            ValueKind::Float
+        ]
+    );
+
+    // Values which are read in order are peeked while streaming
+    let in_order = r#"{ "a": null, "b": true, "c": "x", "d": {}, "e": [], "f": 10, "g": 1.5 }"#;
+    assert_eq!(kinds(in_order), kinds(source));
+
+    // Peeking a streaming number doesn't consume it, and agrees with the number that is read
+    struct Peeked(ValueKind, Number);
+    impl<D: ShapeDeserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for Peeked {
+        const NULLABLE: bool = false;
+        fn deserialize(mut value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+            let kind = value.peek_kind()?;
+            let (d, done_flag) = value.into_raw();
+            let number = d.get_number()?;
+            *done_flag = true;
+            Ok(Peeked(kind, number))
+        }
+    }
+    let source = "[3, 1.5, -2, 1e3, 2.5e-1, 1.0, -0.0, 10.00, -0]";
+    let items = from_str::<Vec<Peeked>>(source).unwrap();
+    assert_eq!(
+        items.iter().map(|item| (item.0, item.1)).collect::<Vec<_>>(),
+        [
+            (ValueKind::Integer, Number::Unsigned(3)),
+            (ValueKind::Float, Number::Float(1.5)),
+            (ValueKind::Integer, Number::Signed(-2)),
+            (ValueKind::Float, Number::Float(1000.0)),
+            (ValueKind::Float, Number::Float(0.25)),
+            (ValueKind::Float, Number::Float(1.0)),
+            (ValueKind::Float, Number::Float(-0.0)),
+            (ValueKind::Float, Number::Float(10.0)),
+            (ValueKind::Integer, Number::Unsigned(0))
+        ]
+    );
+    assert!(matches!(items[6].1, Number::Float(x) if x.is_sign_negative()));
+}
+
+#[test]
+fn test_speculate() {
+    use serdere::{ErrorCollector, PathTracker};
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Circle {
+        radius: f64,
+    }
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Rect {
+        width: u32,
+        height: u32,
+    }
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Circle(Circle),
+        Rect(Rect),
+        Named(String),
+    }
+    impl<D: Deserializer + ?Sized, Ctx: ?Sized> Deserialize<D, Ctx> for Shape {
+        const NULLABLE: bool = false;
+        fn deserialize(mut value: Value<D>, _: &mut Ctx) -> Result<Self, D::Error> {
+            if let Some(rect) = value.speculate(|value| value.get::<Rect>())? {
+                return Ok(Shape::Rect(rect));
+            }
+            if let Some(circle) = value.speculate(|value| value.get::<Circle>())? {
+                return Ok(Shape::Circle(circle));
+            }
+            Ok(Shape::Named(value.get_str()?.into_owned()))
+        }
+    }
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Scene {
+        background: Shape,
+        shapes: Vec<Shape>,
+    }
+    let source = r#"{
+        "shapes": [{ "radius": 1.5 }, { "width": 2, "height": 3 }, "dot", { "width": -1 }],
+        "background": { "height": 4, "width": 5 }
+    }"#;
+    let expected = Scene {
+        background: Shape::Rect(Rect {
+            width: 5,
+            height: 4,
+        }),
+        shapes: vec![
+            Shape::Circle(Circle { radius: 1.5 }),
+            Shape::Rect(Rect {
+                width: 2,
+                height: 3,
+            }),
+            Shape::Named("dot".to_owned()),
+        ],
+    };
+    assert!(from_str::<Scene>(source).is_err());
+    let source = source.replace(r#", { "width": -1 }"#, "");
+    assert_eq!(from_str::<Scene>(&source).unwrap(), expected);
+    assert_eq!(from_str::<Shape>("\"dot\"").unwrap(), Shape::Named("dot".to_owned()));
+
+    // Errors recovered from during a failed attempt are discarded
+    let d = TextDeserializer::new(TextDeserializerConfig::default(), source.as_str()).unwrap();
+    let mut d = ErrorCollector::new(PathTracker::new(d));
+    let res = Value::with(&mut d, |value| value.get::<Scene>());
+    let (scene, d) = d.finish(res).unwrap();
+    d.into_inner().close().unwrap();
+    assert_eq!(scene, expected);
+
+    // Syntax errors are reported when the checkpoint is taken
+    assert!(from_str::<Shape>(r#"{ "radius": 1.5 "#).is_err());
+
+    // Rewinding restores objects which were left partially read
+    let source = r#"[{ "a": 1, "b": { "c": "x", "d": 2 } }]"#;
+    let mut d = TextDeserializer::new(TextDeserializerConfig::default(), source).unwrap();
+    d.open_list().unwrap();
+    assert!(d.next_item().unwrap());
+    assert!(d.checkpoint().unwrap());
+    d.open_object().unwrap();
+    assert!(d.try_push_entry("b").unwrap());
+    d.open_object().unwrap();
+    assert!(d.try_push_entry("c").unwrap());
+    assert!(d.get_u32().is_err());
+    d.rewind();
+    d.open_object().unwrap();
+    d.push_entry("b").unwrap();
+    d.open_object().unwrap();
+    d.push_entry("d").unwrap();
+    assert_eq!(d.get_u32().unwrap(), 2);
+    d.push_entry("c").unwrap();
+    assert_eq!(d.read_str().unwrap(), "x");
+    d.close_object().unwrap();
+    d.push_entry("a").unwrap();
+    assert_eq!(d.get_u32().unwrap(), 1);
+    d.close_object().unwrap();
+    assert!(!d.next_item().unwrap());
+    d.close().unwrap();
+}
+
+#[test]
+fn test_display_fromstr() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Package {
+        name: String,
+        #[serde(display_fromstr)]
+        build: u32,
+        #[serde(display_fromstr, default)]
+        checksum: u64,
+    }
+    assert_eq!(
+        from_str::<Package>(r#"{ "name": "serdere", "build": "42", "checksum": "7" }"#).unwrap(),
+        Package {
+            name: "serdere".to_string(),
+            build: 42,
+            checksum: 7,
+        }
+    );
+    assert_eq!(
+        from_str::<Package>(r#"{ "name": "serdere", "build": "42", "checksum": null }"#)
+            .unwrap()
+            .checksum,
+        0
+    );
+    assert_eq!(
+        from_str::<Package>(r#"{ "name": "serdere", "build": "x", "checksum": null }"#)
+            .unwrap_err()
+            .message()
+            .to_string(),
+        "invalid digit found in string"
+    );
+}
@@ -6,7 +6,7 @@ fn test_f32_roundtrip_exhaustive() {
     for i in 0..u32::MAX {
         let exp = f32::from_bits(i);
         if exp.is_finite() {
-            let s = to_str(&exp);
+            let s = to_str(&exp).unwrap();
             let act = from_str(&s).unwrap();
             assert_eq!(exp, act);
         }
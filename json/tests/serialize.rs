@@ -1,46 +1,151 @@
-use serdere_json::{to_str, TextSerializer, TextSerializerConfig};
+use serdere_json::{to_str, BytesEncoding, SerializeError, TextSerializer, TextSerializerConfig};
+use serdere::{Outliner, Serializer};
 use serdere_json::{ValueExt, ValueSerialierExt};
-use serdere::{Serialize, Value};
+use serdere::{Bytes, Serialize, Value};
 use indoc::*;
 
 #[test]
 fn test_bool() {
-    assert_eq!(to_str(&true), "true");
-    assert_eq!(to_str(&false), "false");
+    assert_eq!(to_str(&true).unwrap(), "true");
+    assert_eq!(to_str(&false).unwrap(), "false");
 }
 
 #[test]
 fn test_str() {
-    assert_eq!(to_str("Hello world!"), "\"Hello world!\"");
-    assert_eq!(to_str("\t\n"), "\"\\t\\n\"");
+    assert_eq!(to_str("Hello world!").unwrap(), "\"Hello world!\"");
+    assert_eq!(to_str("\t\n").unwrap(), "\"\\t\\n\"");
 }
 
 #[test]
 fn test_number() {
-    assert_eq!(to_str::<u32>(&1234), "1234");
-    assert_eq!(to_str::<i32>(&-1400), "-1400");
-    assert_eq!(to_str::<f32>(&1.5), "1.5");
-    assert_eq!(to_str::<f32>(&-0.125), "-0.125");
+    assert_eq!(to_str::<u32>(&1234).unwrap(), "1234");
+    assert_eq!(to_str::<i32>(&-1400).unwrap(), "-1400");
+    assert_eq!(to_str::<f32>(&1.5).unwrap(), "1.5");
+    assert_eq!(to_str::<f32>(&-0.125).unwrap(), "-0.125");
+    assert_eq!(to_str::<u128>(&u128::MAX).unwrap(), "340282366920938463463374607431768211455");
+    assert_eq!(to_str::<i128>(&i128::MIN).unwrap(), "-170141183460469231731687303715884105728");
+}
+
+#[test]
+fn test_bytes() {
+    assert_eq!(to_str(&Bytes(b"")).unwrap(), "\"\"");
+    assert_eq!(to_str(&Bytes(b"f")).unwrap(), "\"Zg==\"");
+    assert_eq!(to_str(&Bytes(b"fo")).unwrap(), "\"Zm8=\"");
+    assert_eq!(to_str(&Bytes(b"foo")).unwrap(), "\"Zm9v\"");
+    assert_eq!(to_str(&Bytes(b"foobar")).unwrap(), "\"Zm9vYmFy\"");
+    assert_eq!(to_str(&Bytes(vec![0xFB, 0xFF])).unwrap(), "\"+/8=\"");
+
+    let to_str_with = |encoding, chunks: &[&[u8]]| {
+        let mut res = String::new();
+        let mut s = TextSerializer::new(
+            TextSerializerConfig {
+                bytes_encoding: encoding,
+                ..Default::default()
+            },
+            &mut res,
+        );
+        Value::with(&mut s, |value| {
+            let (s, done) = value.into_raw();
+            s.open_bytes()?;
+            for chunk in chunks {
+                s.append_bytes(chunk)?;
+            }
+            s.close_bytes()?;
+            *done = true;
+            Ok(())
+        })
+        .unwrap();
+        res
+    };
+    assert_eq!(to_str_with(BytesEncoding::Base64, &[b"f", b"oob", b"a", b"r"]), "\"Zm9vYmFy\"");
+    assert_eq!(to_str_with(BytesEncoding::Base64Url, &[&[0xFB], &[0xFF]]), "\"-_8\"");
+    assert_eq!(to_str_with(BytesEncoding::Hex, &[&[0x00, 0xFF], &[0x7A]]), "\"00ff7a\"");
 }
 
 #[test]
 fn test_tuple() {
-    assert_eq!(to_str::<[u32; 3]>(&[3, 6, 9]), "[3, 6, 9]");
-    assert_eq!(to_str::<(u32, bool)>(&(3, false)), "[3, false]");
+    assert_eq!(to_str::<[u32; 3]>(&[3, 6, 9]).unwrap(), "[3, 6, 9]");
+    assert_eq!(to_str::<(u32, bool)>(&(3, false)).unwrap(), "[3, false]");
+    assert_eq!(to_str::<(u32,)>(&(3,)).unwrap(), "[3]");
+    assert_eq!(
+        to_str(&(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, "x".to_string())).unwrap(),
+        r#"[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, "x"]"#
+    );
+}
+
+#[test]
+fn test_collections() {
+    use std::collections::{BTreeSet, LinkedList, VecDeque};
+    assert_eq!(to_str::<[u32]>(&[1, 2, 3]).unwrap(), "[1, 2, 3]");
+    assert_eq!(to_str::<Box<[u32]>>(&vec![1, 2].into_boxed_slice()).unwrap(), "[1, 2]");
+    assert_eq!(to_str(&VecDeque::from([1, 2, 3])).unwrap(), "[1, 2, 3]");
+    assert_eq!(to_str(&LinkedList::from([1, 2, 3])).unwrap(), "[1, 2, 3]");
+    assert_eq!(to_str(&BTreeSet::from([3, 1, 2])).unwrap(), "[1, 2, 3]");
+}
+
+#[test]
+fn test_map() {
+    use std::collections::{BTreeMap, HashMap};
+    assert_eq!(
+        to_str(&BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)])).unwrap(),
+        r#"{ "a": 1, "b": 2 }"#
+    );
+    assert_eq!(
+        to_str(&BTreeMap::from([(1, true), (2, false)])).unwrap(),
+        "[[1, true], [2, false]]"
+    );
+    assert_eq!(
+        to_str(&BTreeMap::from([((1, 2), "x".to_string())])).unwrap(),
+        r#"[[[1, 2], "x"]]"#
+    );
+    assert_eq!(to_str(&BTreeMap::<u32, u32>::new()).unwrap(), "[]");
+    assert_eq!(
+        to_str(&BTreeMap::from([("a".to_string(), BTreeMap::from([(1, 2)]))])).unwrap(),
+        r#"{ "a": [[1, 2]] }"#
+    );
+
+    // The representation of a map is determined by its key type, not its entries
+    let mixed = BTreeMap::from([(None, 1), (Some("a".to_string()), 2)]);
+    assert_eq!(to_str(&mixed).unwrap(), r#"[[null, 1], ["a", 2]]"#);
+    assert_eq!(
+        to_str(&BTreeMap::from([(Some("a".to_string()), 2)])).unwrap(),
+        r#"[["a", 2]]"#
+    );
+    for _ in 0..20 {
+        let mixed = HashMap::from([(None, 1), (Some("a".to_string()), 2)]);
+        let res = to_str(&mixed).unwrap();
+        assert!(res == r#"[[null, 1], ["a", 2]]"# || res == r#"[["a", 2], [null, 1]]"#);
+    }
+    assert_eq!(to_str(&BTreeMap::<String, u32>::new()).unwrap(), "{}");
+
+    // A key type which claims to be a string must write one
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    struct Key(u32);
+    impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Key {
+        const NULLABLE: bool = false;
+        const IS_STR: bool = true;
+        fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+            value.put_u32(self.0)
+        }
+    }
+    assert!(matches!(
+        to_str(&BTreeMap::from([(Key(1), 2)])),
+        Err(SerializeError::NonStringKey)
+    ));
 }
 
 #[test]
 fn test_option() {
     assert_eq!(
-        to_str::<Option<Option<bool>>>(&Some(Some(true))),
+        to_str::<Option<Option<bool>>>(&Some(Some(true))).unwrap(),
         "{ \"has_value\": true, \"value\": true }"
     );
     assert_eq!(
-        to_str::<Option<Option<bool>>>(&Some(None)),
+        to_str::<Option<Option<bool>>>(&Some(None)).unwrap(),
         "{ \"has_value\": true, \"value\": null }"
     );
     assert_eq!(
-        to_str::<Option<Option<bool>>>(&None),
+        to_str::<Option<Option<bool>>>(&None).unwrap(),
         "{ \"has_value\": false }"
     );
 }
@@ -51,6 +156,7 @@ fn test_object_simple() {
     let mut s = TextSerializer::new(
         TextSerializerConfig {
             indent: Some("    "),
+            ..Default::default()
         },
         &mut res,
     );
@@ -80,6 +186,84 @@ fn test_object_simple() {
     assert_eq!(res, expected);
 }
 
+#[test]
+fn test_std_types() {
+    use std::cmp::Ordering;
+    use std::ops::Bound;
+    use std::time::{Duration, UNIX_EPOCH};
+    assert_eq!(to_str(&Duration::new(3, 500)).unwrap(), r#"{ "secs": 3, "nanos": 500 }"#);
+    assert_eq!(
+        to_str(&(UNIX_EPOCH + Duration::new(10, 1))).unwrap(),
+        r#"{ "secs": 10, "nanos": 1 }"#
+    );
+    assert_eq!(
+        to_str(&(UNIX_EPOCH - Duration::new(1, 750_000_000))).unwrap(),
+        r#"{ "secs": -2, "nanos": 250000000 }"#
+    );
+
+    // The earliest time on Unix platforms doesn't overflow
+    #[cfg(unix)]
+    assert_eq!(
+        to_str(&(UNIX_EPOCH - Duration::from_secs(i64::MAX as u64) - Duration::from_secs(1)))
+            .unwrap(),
+        r#"{ "secs": -9223372036854775808, "nanos": 0 }"#
+    );
+    assert_eq!(to_str(&(1..4)).unwrap(), r#"{ "start": 1, "end": 4 }"#);
+    assert_eq!(to_str(&(1..=4)).unwrap(), r#"{ "start": 1, "end": 4 }"#);
+    assert_eq!(to_str(&Bound::Included(3)).unwrap(), r#"{ "type": "Included", "value": 3 }"#);
+    assert_eq!(to_str(&Bound::<u32>::Unbounded).unwrap(), r#"{ "type": "Unbounded" }"#);
+    assert_eq!(to_str(&Ok::<u32, String>(3)).unwrap(), r#"{ "type": "Ok", "value": 3 }"#);
+    assert_eq!(
+        to_str(&Err::<u32, String>("bad".to_string())).unwrap(),
+        r#"{ "type": "Err", "value": "bad" }"#
+    );
+    assert_eq!(to_str(&Ordering::Less).unwrap(), "\"Less\"");
+}
+
+#[test]
+fn test_string_types() {
+    use serdere::CharRange;
+    use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+    use std::path::Path;
+    assert_eq!(to_str(&IpAddr::from([127, 0, 0, 1])).unwrap(), "\"127.0.0.1\"");
+    assert_eq!(to_str(&Ipv6Addr::LOCALHOST).unwrap(), "\"::1\"");
+    assert_eq!(to_str(&SocketAddr::from(([10, 0, 0, 1], 8080))).unwrap(), "\"10.0.0.1:8080\"");
+    assert_eq!(to_str(Path::new("/etc/hosts")).unwrap(), "\"/etc/hosts\"");
+    assert_eq!(to_str(&CharRange('a'..='z')).unwrap(), "\"a-z\"");
+    assert_eq!(to_str(&('a'..='z')).unwrap(), r#"{ "start": "a", "end": "z" }"#);
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        assert_eq!(to_str(OsStr::from_bytes(b"a\xFFb")).unwrap(), "\"\u{FFFF}61ff62\"");
+    }
+}
+
+#[test]
+fn test_wrappers() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    assert_eq!(to_str(&Box::new(1)).unwrap(), "1");
+    assert_eq!(to_str::<Rc<str>>(&Rc::from("abc")).unwrap(), "\"abc\"");
+    assert_eq!(to_str::<Arc<[u32]>>(&Arc::from(vec![1, 2])).unwrap(), "[1, 2]");
+    assert_eq!(to_str(&RefCell::new(true)).unwrap(), "true");
+    let cell = RefCell::new(true);
+    let _borrow = cell.borrow_mut();
+    assert!(matches!(to_str(&cell), Err(SerializeError::Custom(_))));
+    assert_eq!(to_str(&Mutex::new(2)).unwrap(), "2");
+    assert_eq!(to_str(&std::num::Saturating(3u8)).unwrap(), "3");
+    assert_eq!(to_str(&std::marker::PhantomData::<u32>).unwrap(), "{}");
+
+    // `null` optimization should pass through wrappers
+    assert_eq!(to_str::<Option<Box<u32>>>(&None).unwrap(), "null");
+    assert_eq!(
+        to_str::<Option<Arc<Option<u32>>>>(&Some(Arc::new(None))).unwrap(),
+        "{ \"has_value\": true, \"value\": null }"
+    );
+}
+
 #[test]
 fn test_derive_struct() {
     #[derive(PartialEq, Eq, Debug, Serialize)]
@@ -94,7 +278,7 @@ fn test_derive_struct() {
             name: "Mike".to_string(),
             age: 28,
             data_xyz: false
-        }),
+        }).unwrap(),
         r#"{ "name": "Mike", "age": 28, "data": false }"#
     );
 }
@@ -111,11 +295,11 @@ fn test_derive_enum_simple() {
         #[serde(rename = "option_5")]
         OptionV,
     }
-    assert_eq!(to_str::<Test>(&Test::OptionA), "\"OptionA\"");
-    assert_eq!(to_str::<Test>(&Test::Option2), "\"Option2\"");
-    assert_eq!(to_str::<Test>(&Test::TheThirdOption), "\"TheThirdOption\"");
-    assert_eq!(to_str::<Test>(&Test::OptionIV), "\"option_4\"");
-    assert_eq!(to_str::<Test>(&Test::OptionV), "\"option_5\"");
+    assert_eq!(to_str::<Test>(&Test::OptionA).unwrap(), "\"OptionA\"");
+    assert_eq!(to_str::<Test>(&Test::Option2).unwrap(), "\"Option2\"");
+    assert_eq!(to_str::<Test>(&Test::TheThirdOption).unwrap(), "\"TheThirdOption\"");
+    assert_eq!(to_str::<Test>(&Test::OptionIV).unwrap(), "\"option_4\"");
+    assert_eq!(to_str::<Test>(&Test::OptionV).unwrap(), "\"option_5\"");
 }
 
 #[test]
@@ -133,7 +317,7 @@ fn test_derive_enum_complex() {
         },
     }
     assert_eq!(
-        to_str::<Test<bool>>(&Test::Unassigned),
+        to_str::<Test<bool>>(&Test::Unassigned).unwrap(),
         r#"{ "type": "unassigned" }"#
     );
     assert_eq!(
@@ -141,7 +325,7 @@ fn test_derive_enum_complex() {
             name: "Mike".to_string(),
             age: 28,
             data_xyz: false
-        }),
+        }).unwrap(),
         r#"{ "type": "assigned", "name": "Mike", "age": 28, "data": false }"#
     );
 }
@@ -170,13 +354,13 @@ fn test_derive_enum_transparent() {
         #[serde(rename = "rect", transparent)]
         Rect { source: Rect },
     }
-    assert_eq!(to_str::<Shape>(&Shape::Other), r#"{ "type": "other" }"#);
+    assert_eq!(to_str::<Shape>(&Shape::Other).unwrap(), r#"{ "type": "other" }"#);
     assert_eq!(
         to_str::<Shape>(&Shape::Circle(Circle {
             x: 10,
             y: 20,
             radius: 30
-        })),
+        })).unwrap(),
         r#"{ "type": "circle", "x": 10, "y": 20, "radius": 30 }"#
     );
     assert_eq!(
@@ -187,7 +371,221 @@ fn test_derive_enum_transparent() {
                 width: 30,
                 height: 40
             }
-        }),
+        }).unwrap(),
         r#"{ "type": "rect", "x": 10, "y": -20, "width": 30, "height": 40 }"#
     );
 }
+
+#[test]
+fn test_tree() {
+    use serdere::{from_tree, to_tree, Deserialize, Tree};
+    use serdere_json::from_str;
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Shape {
+        #[serde(rename = "circle")]
+        Circle { radius: u32 },
+        #[serde(rename = "polygon")]
+        Polygon { points: Vec<(i32, i32)> },
+    }
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Scene {
+        name: String,
+        scale: Option<f64>,
+        shapes: Vec<Shape>,
+    }
+    let scene = Scene {
+        name: "test".to_owned(),
+        scale: None,
+        shapes: vec![
+            Shape::Circle { radius: 5 },
+            Shape::Polygon {
+                points: vec![(0, 0), (1, 0), (0, -1)],
+            },
+        ],
+    };
+    let tree = to_tree(&scene).unwrap();
+    assert_eq!(to_str(&tree).unwrap(), to_str(&scene).unwrap());
+    assert_eq!(from_tree::<Scene>(&tree).unwrap(), scene);
+    assert!(from_tree::<Shape>(&tree).is_err());
+
+    // A tree read from JSON can be read back as the original type
+    let json = to_str(&scene).unwrap();
+    let tree = from_str::<Tree>(&json).unwrap();
+    assert_eq!(from_tree::<Scene>(&tree).unwrap(), scene);
+    assert_eq!(to_str(&tree).unwrap(), json);
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Point {
+        x: u32,
+        name: String,
+    }
+    let tree = from_str::<Tree>(r#"{ "name": "a", "x": 1 }"#).unwrap();
+    assert_eq!(
+        from_tree::<Point>(&tree).unwrap(),
+        Point {
+            x: 1,
+            name: "a".to_owned()
+        }
+    );
+    let tree = from_str::<Tree>(r#"{ "x": 1 }"#).unwrap();
+    assert_eq!(from_tree::<Point>(&tree).unwrap_err().to_string(), r#"missing field "name""#);
+    let tree = from_str::<Tree>(r#"{ "x": 1, "name": "a", "y": 2 }"#).unwrap();
+    assert_eq!(from_tree::<Point>(&tree).unwrap_err().to_string(), r#"unexpected field "y""#);
+    let tree = from_str::<Tree>("[1, 2]").unwrap();
+    assert_eq!(from_tree::<(u32, u32)>(&tree).unwrap(), (1, 2));
+    assert!(from_tree::<(u32, u32, u32)>(&tree).is_err());
+    assert_eq!(
+        to_str(&Tree::List(vec![Tree::U8(1), Tree::Null, Tree::Str("a".to_owned())])).unwrap(),
+        r#"[1, null, "a"]"#
+    );
+}
+
+#[test]
+fn test_erased() {
+    use serdere::ErasedSerialize;
+    let values: Vec<Box<dyn ErasedSerialize>> = vec![
+        Box::new(1u32),
+        Box::new("two".to_owned()),
+        Box::new(vec![3.5f64]),
+        Box::new(Some(false)),
+    ];
+    assert_eq!(to_str(&values).unwrap(), r#"[1, "two", [3.5], false]"#);
+}
+
+#[test]
+fn test_custom_error() {
+    use serdere_json::SerializeError;
+    struct Finite(f64);
+    impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Finite {
+        const NULLABLE: bool = false;
+        fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+            if self.0.is_finite() {
+                value.put_f64(self.0)
+            } else {
+                Err(value.as_raw().error("value is not finite".into()))
+            }
+        }
+    }
+    assert_eq!(to_str(&[Finite(1.5)]).unwrap(), "[1.5]");
+    let err = to_str(&[Finite(1.5), Finite(f64::NAN)]).unwrap_err();
+    assert!(matches!(err, SerializeError::Custom(_)));
+    assert_eq!(err.to_string(), "value is not finite");
+    assert!(std::error::Error::source(&err).is_some());
+
+    // Writer errors are exposed as the source
+    let err = SerializeError::Writer(std::fmt::Error);
+    assert!(std::error::Error::source(&err).is_some_and(|source| source.is::<std::fmt::Error>()));
+}
+
+#[test]
+fn test_human_readable() {
+    struct Version(u8, u8);
+    impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Version {
+        const NULLABLE: bool = false;
+        fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+            if value.is_human_readable() {
+                value.put_str(&format!("{}.{}", self.0, self.1))
+            } else {
+                value.put(&(self.0, self.1))
+            }
+        }
+    }
+    assert_eq!(to_str(&[Version(1, 2)]).unwrap(), r#"["1.2"]"#);
+}
+
+#[test]
+fn test_struct_fields() {
+    use serdere::{record, Event};
+    #[derive(Serialize)]
+    struct Point {
+        x: i32,
+        #[serde(rename = "Y")]
+        y: i32,
+    }
+    #[derive(Serialize)]
+    struct Labeled {
+        label: String,
+        #[serde(flatten)]
+        point: Point,
+    }
+    let events = |log: Vec<serdere::Record>| {
+        log.into_iter()
+            .filter(|record| record.depth == 0)
+            .map(|record| record.event)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        events(record(&Point { x: 1, y: 2 }).unwrap())[0],
+        Event::OpenStructWithFields(Some("Point"), &["x", "Y"])
+    );
+
+    // Flattened fields can't be listed up front
+    let labeled = Labeled {
+        label: "origin".to_owned(),
+        point: Point { x: 0, y: 0 },
+    };
+    assert_eq!(
+        events(record(&labeled).unwrap())[0],
+        Event::OpenStruct(Some("Labeled"))
+    );
+}
+
+#[test]
+fn test_put_iter() {
+    use serdere::{record, Event, SerializeIter};
+    let rows = [(1, "alpha"), (2, "beta"), (3, "gamma")];
+
+    // Iterators are streamed, and can be serialized more than once
+    #[derive(Serialize)]
+    struct Query<I: Iterator<Item = &'static str> + Clone> {
+        names: SerializeIter<I>,
+    }
+    let query = Query {
+        names: SerializeIter::new(rows.iter().filter(|row| row.0 != 2).map(|row| row.1)),
+    };
+    assert_eq!(to_str(&query).unwrap(), r#"{ "names": ["alpha", "gamma"] }"#);
+    assert_eq!(to_str(&query).unwrap(), r#"{ "names": ["alpha", "gamma"] }"#);
+    let log = record(&query).unwrap();
+    assert_eq!(log[2].event, Event::OpenListUnsized);
+
+    // Iterators of known length can be written with their length
+    struct Ids<'a>(&'a [(i32, &'static str)]);
+    impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Ids<'_> {
+        const NULLABLE: bool = false;
+        fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+            value.put_exact_iter(self.0.iter().map(|row| row.0))
+        }
+    }
+    let log = record(&Ids(&rows)).unwrap();
+    assert_eq!(log[0].event, Event::OpenListSized(3));
+    assert_eq!(to_str(&Ids(&rows)).unwrap(), "[1, 2, 3]");
+
+    struct Index<'a>(&'a [(i32, &'static str)]);
+    impl<S: Serializer + ?Sized, Ctx: ?Sized> Serialize<S, Ctx> for Index<'_> {
+        const NULLABLE: bool = false;
+        fn serialize(&self, value: Value<S>, _: &mut Ctx) -> Result<(), S::Error> {
+            value.put_map_iter(self.0.iter().filter(|row| row.0 > 1).map(|row| (row.1, row.0)))
+        }
+    }
+    assert_eq!(to_str(&Index(&rows)).unwrap(), r#"{ "beta": 2, "gamma": 3 }"#);
+}
+
+#[test]
+fn test_put_display() {
+    struct Version(u8, u8);
+    impl std::fmt::Display for Version {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "v{}.{}", self.0, self.1)
+        }
+    }
+    #[derive(Serialize)]
+    struct Package {
+        name: String,
+        #[serde(display_fromstr)]
+        version: Version,
+    }
+    let package = Package {
+        name: "serdere".to_owned(),
+        version: Version(1, 2),
+    };
+    assert_eq!(to_str(&package).unwrap(), r#"{ "name": "serdere", "version": "v1.2" }"#);
+}